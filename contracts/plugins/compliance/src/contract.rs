@@ -0,0 +1,323 @@
+use cosmwasm_std::{
+    Addr, BankMsg, CosmosMsg, Deps, DistributionMsg, Empty, Env, Event, IbcMsg, Order, Response,
+    StdError, StdResult, WasmMsg,
+};
+use cw2::{get_contract_version, set_contract_version, ContractVersion};
+use cw_storage_plus::{Bound, Item, Map};
+use sylvia::{
+    contract, schemars,
+    types::{ExecCtx, InstantiateCtx, MigrateCtx, QueryCtx},
+};
+
+// Vectis lib
+use vectis_compliance_denylist::contract::sv::QueryMsg as ComplianceDenylistQueryMsg;
+use vectis_wallet::{
+    interface::{pre_tx_check_trait, PreTxCheckTrait},
+    DEFAULT_LIMIT, MAX_LIMIT,
+};
+
+use crate::{
+    error::ComplianceError,
+    types::{
+        AddressStatusResponse, AllowlistResponse, BlockReason, BlockedMsg, BlockedMsgsResponse,
+        ConfigResponse,
+    },
+};
+
+#[cfg(not(feature = "library"))]
+use sylvia::entry_points;
+
+const CONTRACT_NAME: &str = env!("CARGO_PKG_NAME");
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// PreTxCheck plugin that blocks txs sending to addresses that are not compliant.
+///
+/// The recipients are `BankMsg::Send` receivers, `WasmMsg::Execute` targets (with or without funds),
+/// `IbcMsg::Transfer` receivers and the distribution withdraw address.
+/// Msgs that can move funds without such a recipient (instantiate with funds, Stargate) are blocked.
+/// The denylist is shared and maintained by the compliance operator in `vectis-compliance-denylist`,
+/// the owner (proxy) maintains an allowlist which, when enabled, restricts the recipients.
+/// The proxy itself and this plugin are never blocked so the plugin can always be managed.
+pub struct Compliance<'a> {
+    owner: Item<'a, Addr>,
+    denylist: Item<'a, Addr>,
+    allowlist_enabled: Item<'a, bool>,
+    /// Lowercased addresses
+    allowlist: Map<'a, &'a str, Empty>,
+}
+
+impl Default for Compliance<'_> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+mod pretxchecktrait {
+    use super::*;
+
+    #[contract(module=crate::contract)]
+    #[messages(pre_tx_check_trait as PreTxCheckTrait)]
+    #[error(ComplianceError)]
+    impl PreTxCheckTrait for Compliance<'_> {
+        type Error = ComplianceError;
+
+        #[msg(query)]
+        fn pre_tx_check(&self, ctx: QueryCtx, msgs: Vec<CosmosMsg>) -> Result<bool, Self::Error> {
+            Ok(self.blocked_msgs(ctx.deps, &ctx.env, &msgs)?.is_empty())
+        }
+
+        #[msg(query)]
+        fn contract_version(&self, ctx: QueryCtx) -> Result<ContractVersion, StdError> {
+            get_contract_version(ctx.deps.storage)
+        }
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_points)]
+#[contract]
+#[error(ComplianceError)]
+#[messages(pre_tx_check_trait as PreTxCheckTrait)]
+impl Compliance<'_> {
+    pub const fn new() -> Self {
+        Self {
+            owner: Item::new("owner"),
+            denylist: Item::new("denylist"),
+            allowlist_enabled: Item::new("allowlist_enabled"),
+            allowlist: Map::new("allowlist"),
+        }
+    }
+
+    /// `denylist` is the shared `vectis-compliance-denylist` contract
+    #[msg(instantiate)]
+    fn instantiate(
+        &self,
+        ctx: InstantiateCtx,
+        denylist: String,
+        allowlist: Vec<String>,
+        allowlist_enabled: bool,
+    ) -> Result<Response, ComplianceError> {
+        set_contract_version(ctx.deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+        let denylist = ctx.deps.api.addr_validate(&denylist)?;
+        self.owner.save(ctx.deps.storage, &ctx.info.sender)?;
+        self.denylist.save(ctx.deps.storage, &denylist)?;
+        self.allowlist_enabled
+            .save(ctx.deps.storage, &allowlist_enabled)?;
+        for address in allowlist {
+            self.allowlist
+                .save(ctx.deps.storage, &address.to_lowercase(), &Empty {})?;
+        }
+
+        let event = self
+            .get_event("instantiate")
+            .add_attribute("denylist", denylist);
+        Ok(Response::new().add_event(event))
+    }
+
+    #[msg(migrate)]
+    fn migrate(&self, ctx: MigrateCtx) -> Result<Response, ComplianceError> {
+        set_contract_version(ctx.deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+        let event = self.get_event("migrate");
+        Ok(Response::new().add_event(event))
+    }
+
+    /// Permission: owner (proxy)
+    #[msg(exec)]
+    fn update_allowlist(
+        &self,
+        ctx: ExecCtx,
+        add: Vec<String>,
+        remove: Vec<String>,
+    ) -> Result<Response, ComplianceError> {
+        self.ensure_owner(ctx.deps.as_ref(), &ctx.info.sender)?;
+
+        for address in remove {
+            self.allowlist
+                .remove(ctx.deps.storage, &address.to_lowercase());
+        }
+        for address in add {
+            self.allowlist
+                .save(ctx.deps.storage, &address.to_lowercase(), &Empty {})?;
+        }
+
+        let event = self.get_event("update_allowlist");
+        Ok(Response::new().add_event(event))
+    }
+
+    /// Permission: owner (proxy)
+    #[msg(exec)]
+    fn set_allowlist_enabled(
+        &self,
+        ctx: ExecCtx,
+        enabled: bool,
+    ) -> Result<Response, ComplianceError> {
+        self.ensure_owner(ctx.deps.as_ref(), &ctx.info.sender)?;
+        self.allowlist_enabled.save(ctx.deps.storage, &enabled)?;
+
+        let event = self
+            .get_event("set_allowlist_enabled")
+            .add_attribute("enabled", enabled.to_string());
+        Ok(Response::new().add_event(event))
+    }
+
+    #[msg(query)]
+    fn config(&self, ctx: QueryCtx) -> Result<ConfigResponse, ComplianceError> {
+        Ok(ConfigResponse {
+            owner: self.owner.load(ctx.deps.storage)?,
+            denylist: self.denylist.load(ctx.deps.storage)?,
+            allowlist_enabled: self.allowlist_enabled.load(ctx.deps.storage)?,
+        })
+    }
+
+    /// Explains why an address would be blocked as a recipient
+    #[msg(query)]
+    fn address_status(
+        &self,
+        ctx: QueryCtx,
+        address: String,
+    ) -> Result<AddressStatusResponse, ComplianceError> {
+        let blocked = self.block_reason(ctx.deps, &ctx.env, &address)?;
+        Ok(AddressStatusResponse { address, blocked })
+    }
+
+    /// Returns the msgs that would be blocked and why, once per blocked recipient
+    #[msg(query)]
+    fn check_msgs(
+        &self,
+        ctx: QueryCtx,
+        msgs: Vec<CosmosMsg>,
+    ) -> Result<BlockedMsgsResponse, ComplianceError> {
+        let blocked = self.blocked_msgs(ctx.deps, &ctx.env, &msgs)?;
+        Ok(BlockedMsgsResponse { blocked })
+    }
+
+    #[msg(query)]
+    fn allowlist(
+        &self,
+        ctx: QueryCtx,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> Result<AllowlistResponse, ComplianceError> {
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let addresses = self
+            .allowlist
+            .keys(
+                ctx.deps.storage,
+                start_after.as_deref().map(Bound::exclusive),
+                None,
+                Order::Ascending,
+            )
+            .take(limit)
+            .collect::<StdResult<_>>()?;
+
+        Ok(AllowlistResponse { addresses })
+    }
+
+    pub(crate) fn get_event(&self, action: impl Into<String>) -> Event {
+        Event::new("vectis.compliance.v1").add_attribute("action", action)
+    }
+
+    fn ensure_owner(&self, deps: Deps, sender: &Addr) -> Result<(), ComplianceError> {
+        if self.owner.load(deps.storage)? != *sender {
+            return Err(ComplianceError::Unauthorized);
+        }
+        Ok(())
+    }
+
+    fn block_reason(
+        &self,
+        deps: Deps,
+        env: &Env,
+        address: &str,
+    ) -> Result<Option<BlockReason>, ComplianceError> {
+        // The SDK accepts uppercase bech32, so `OSMO1..` is the same account as `osmo1..`
+        let address = address.to_lowercase();
+        if address == self.owner.load(deps.storage)?.as_str()
+            || address == env.contract.address.as_str()
+        {
+            return Ok(None);
+        }
+
+        let reason: Option<String> = deps.querier.query_wasm_smart(
+            self.denylist.load(deps.storage)?,
+            &ComplianceDenylistQueryMsg::Reason {
+                address: address.clone(),
+            },
+        )?;
+        if let Some(reason) = reason {
+            return Ok(Some(BlockReason::Denylisted { reason }));
+        }
+
+        if self.allowlist_enabled.load(deps.storage)? && !self.allowlist.has(deps.storage, &address)
+        {
+            return Ok(Some(BlockReason::NotAllowlisted));
+        }
+
+        Ok(None)
+    }
+
+    fn blocked_msgs(
+        &self,
+        deps: Deps,
+        env: &Env,
+        msgs: &[CosmosMsg],
+    ) -> Result<Vec<BlockedMsg>, ComplianceError> {
+        let mut blocked: Vec<BlockedMsg> = vec![];
+        for msg in msgs {
+            let address = match recipient(msg) {
+                Recipient::Address(address) => address.to_lowercase(),
+                Recipient::Unchecked(msg) => {
+                    blocked.push(BlockedMsg {
+                        address: None,
+                        reason: BlockReason::UncheckedFunds { msg: msg.into() },
+                    });
+                    continue;
+                }
+                Recipient::None => continue,
+            };
+            if blocked
+                .iter()
+                .any(|b| b.address.as_deref() == Some(address.as_str()))
+            {
+                continue;
+            }
+            if let Some(reason) = self.block_reason(deps, env, &address)? {
+                blocked.push(BlockedMsg {
+                    address: Some(address),
+                    reason,
+                });
+            }
+        }
+        Ok(blocked)
+    }
+}
+
+/// What a msg sends funds to or calls
+enum Recipient<'a> {
+    /// The address receiving funds or being called
+    Address(&'a str),
+    /// The msg (type) can move funds but has no recipient that can be checked
+    Unchecked(&'a str),
+    /// The msg does not move funds to anyone
+    None,
+}
+
+fn recipient(msg: &CosmosMsg) -> Recipient<'_> {
+    match msg {
+        CosmosMsg::Bank(BankMsg::Send { to_address, .. }) => Recipient::Address(to_address),
+        CosmosMsg::Wasm(WasmMsg::Execute { contract_addr, .. }) => {
+            Recipient::Address(contract_addr)
+        }
+        CosmosMsg::Wasm(WasmMsg::Instantiate { funds, .. }) if !funds.is_empty() => {
+            Recipient::Unchecked("instantiate")
+        }
+        CosmosMsg::Wasm(WasmMsg::Instantiate2 { funds, .. }) if !funds.is_empty() => {
+            Recipient::Unchecked("instantiate2")
+        }
+        CosmosMsg::Ibc(IbcMsg::Transfer { to_address, .. }) => Recipient::Address(to_address),
+        CosmosMsg::Distribution(DistributionMsg::SetWithdrawAddress { address }) => {
+            Recipient::Address(address)
+        }
+        CosmosMsg::Stargate { type_url, .. } => Recipient::Unchecked(type_url),
+        _ => Recipient::None,
+    }
+}
@@ -0,0 +1,10 @@
+use cosmwasm_std::StdError;
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum ComplianceError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+    #[error("Unauthorized")]
+    Unauthorized,
+}
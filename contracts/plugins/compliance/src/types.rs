@@ -0,0 +1,46 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::Addr;
+
+#[cw_serde]
+pub enum BlockReason {
+    /// Denied by the compliance operator
+    Denylisted { reason: String },
+    /// The allowlist is enabled and the address is not on it
+    NotAllowlisted,
+    /// The msg can move funds but has no recipient that can be checked,
+    /// `msg` is the msg type (e.g. `instantiate` or the Stargate type url)
+    UncheckedFunds { msg: String },
+}
+
+#[cw_serde]
+pub struct BlockedMsg {
+    /// Lowercased, `None` if the msg has no recipient that can be checked
+    pub address: Option<String>,
+    pub reason: BlockReason,
+}
+
+#[cw_serde]
+pub struct AddressStatusResponse {
+    pub address: String,
+    /// `None` if the address is not blocked
+    pub blocked: Option<BlockReason>,
+}
+
+#[cw_serde]
+pub struct BlockedMsgsResponse {
+    pub blocked: Vec<BlockedMsg>,
+}
+
+#[cw_serde]
+pub struct ConfigResponse {
+    pub owner: Addr,
+    /// The shared `vectis-compliance-denylist` contract
+    pub denylist: Addr,
+    pub allowlist_enabled: bool,
+}
+
+#[cw_serde]
+pub struct AllowlistResponse {
+    /// Lowercased addresses
+    pub addresses: Vec<String>,
+}
@@ -0,0 +1,173 @@
+use cosmwasm_std::{Addr, Deps, Event, Order, Response, StdResult};
+use cw2::{get_contract_version, set_contract_version, ContractVersion};
+use cw_storage_plus::{Bound, Item, Map};
+use sylvia::{
+    contract, schemars,
+    types::{ExecCtx, InstantiateCtx, MigrateCtx, QueryCtx},
+};
+
+// Vectis lib
+use vectis_wallet::{DEFAULT_LIMIT, MAX_LIMIT};
+
+use crate::{
+    error::ComplianceDenylistError,
+    types::{DenylistEntry, DenylistResponse},
+};
+
+#[cfg(not(feature = "library"))]
+use sylvia::entry_points;
+
+const CONTRACT_NAME: &str = env!("CARGO_PKG_NAME");
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// Denylist maintained by the compliance operator.
+///
+/// A single instance is shared by all the `vectis-compliance` plugins,
+/// which query it on every `pre_tx_check`.
+/// Addresses are lowercased when stored and looked up.
+pub struct ComplianceDenylist<'a> {
+    operator: Item<'a, Addr>,
+    /// address -> reason
+    denylist: Map<'a, &'a str, String>,
+}
+
+impl Default for ComplianceDenylist<'_> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_points)]
+#[contract]
+#[error(ComplianceDenylistError)]
+impl ComplianceDenylist<'_> {
+    pub const fn new() -> Self {
+        Self {
+            operator: Item::new("operator"),
+            denylist: Map::new("denylist"),
+        }
+    }
+
+    #[msg(instantiate)]
+    fn instantiate(
+        &self,
+        ctx: InstantiateCtx,
+        operator: String,
+    ) -> Result<Response, ComplianceDenylistError> {
+        set_contract_version(ctx.deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+        let operator = ctx.deps.api.addr_validate(&operator)?;
+        self.operator.save(ctx.deps.storage, &operator)?;
+
+        let event = self
+            .get_event("instantiate")
+            .add_attribute("operator", operator);
+        Ok(Response::new().add_event(event))
+    }
+
+    #[msg(migrate)]
+    fn migrate(&self, ctx: MigrateCtx) -> Result<Response, ComplianceDenylistError> {
+        set_contract_version(ctx.deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+        let event = self.get_event("migrate");
+        Ok(Response::new().add_event(event))
+    }
+
+    /// Permission: operator
+    #[msg(exec)]
+    fn update_denylist(
+        &self,
+        ctx: ExecCtx,
+        add: Vec<DenylistEntry>,
+        remove: Vec<String>,
+    ) -> Result<Response, ComplianceDenylistError> {
+        self.ensure_operator(ctx.deps.as_ref(), &ctx.info.sender)?;
+
+        for address in remove {
+            self.denylist
+                .remove(ctx.deps.storage, &address.to_lowercase());
+        }
+        for entry in add {
+            self.denylist.save(
+                ctx.deps.storage,
+                &entry.address.to_lowercase(),
+                &entry.reason,
+            )?;
+        }
+
+        let event = self.get_event("update_denylist");
+        Ok(Response::new().add_event(event))
+    }
+
+    /// Permission: operator
+    #[msg(exec)]
+    fn update_operator(
+        &self,
+        ctx: ExecCtx,
+        operator: String,
+    ) -> Result<Response, ComplianceDenylistError> {
+        self.ensure_operator(ctx.deps.as_ref(), &ctx.info.sender)?;
+
+        let operator = ctx.deps.api.addr_validate(&operator)?;
+        self.operator.save(ctx.deps.storage, &operator)?;
+
+        let event = self
+            .get_event("update_operator")
+            .add_attribute("operator", operator);
+        Ok(Response::new().add_event(event))
+    }
+
+    #[msg(query)]
+    fn operator(&self, ctx: QueryCtx) -> Result<Addr, ComplianceDenylistError> {
+        Ok(self.operator.load(ctx.deps.storage)?)
+    }
+
+    /// Returns why `address` is denied, `None` if it is not on the denylist
+    #[msg(query)]
+    fn reason(
+        &self,
+        ctx: QueryCtx,
+        address: String,
+    ) -> Result<Option<String>, ComplianceDenylistError> {
+        Ok(self
+            .denylist
+            .may_load(ctx.deps.storage, &address.to_lowercase())?)
+    }
+
+    #[msg(query)]
+    fn denylist(
+        &self,
+        ctx: QueryCtx,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> Result<DenylistResponse, ComplianceDenylistError> {
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let entries = self
+            .denylist
+            .range(
+                ctx.deps.storage,
+                start_after.as_deref().map(Bound::exclusive),
+                None,
+                Order::Ascending,
+            )
+            .take(limit)
+            .map(|item| item.map(|(address, reason)| DenylistEntry { address, reason }))
+            .collect::<StdResult<_>>()?;
+
+        Ok(DenylistResponse { entries })
+    }
+
+    #[msg(query)]
+    fn contract_version(&self, ctx: QueryCtx) -> Result<ContractVersion, ComplianceDenylistError> {
+        Ok(get_contract_version(ctx.deps.storage)?)
+    }
+
+    pub(crate) fn get_event(&self, action: impl Into<String>) -> Event {
+        Event::new("vectis.compliance_denylist.v1").add_attribute("action", action)
+    }
+
+    fn ensure_operator(&self, deps: Deps, sender: &Addr) -> Result<(), ComplianceDenylistError> {
+        if self.operator.load(deps.storage)? != *sender {
+            return Err(ComplianceDenylistError::Unauthorized);
+        }
+        Ok(())
+    }
+}
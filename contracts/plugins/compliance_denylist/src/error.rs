@@ -0,0 +1,10 @@
+use cosmwasm_std::StdError;
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum ComplianceDenylistError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+    #[error("Unauthorized")]
+    Unauthorized,
+}
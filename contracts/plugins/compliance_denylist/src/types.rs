@@ -0,0 +1,15 @@
+use cosmwasm_schema::cw_serde;
+
+#[cw_serde]
+pub struct DenylistEntry {
+    /// Addresses can belong to other chains (i.e. IBC receivers),
+    /// they are stored lowercased as bech32 is case insensitive
+    pub address: String,
+    /// Why the address is denied, returned to the user when a tx is blocked
+    pub reason: String,
+}
+
+#[cw_serde]
+pub struct DenylistResponse {
+    pub entries: Vec<DenylistEntry>,
+}
@@ -0,0 +1,410 @@
+use cosmwasm_std::{
+    to_binary, Addr, Api, BankMsg, CosmosMsg, Deps, Empty, Event, Order, Reply, Response,
+    StdResult, Storage, SubMsg, SubMsgResult, Timestamp, WasmMsg,
+};
+use cw2::{get_contract_version, set_contract_version, ContractVersion};
+use cw_storage_plus::{Bound, Item, Map};
+use sylvia::{
+    contract, schemars,
+    types::{ExecCtx, InstantiateCtx, MigrateCtx, QueryCtx, ReplyCtx},
+};
+
+// Vectis lib
+use vectis_wallet::{
+    interface::wallet_plugin_trait::sv::ExecMsg as VectisWalletExecMsg, DEFAULT_LIMIT, MAX_LIMIT,
+};
+
+use crate::{
+    error::RecurringPaymentsError,
+    types::{
+        Schedule, ScheduleParams, ScheduleResponse, SchedulesResponse, UpcomingPayment,
+        UpcomingPaymentsResponse,
+    },
+};
+
+#[cfg(not(feature = "library"))]
+use sylvia::entry_points;
+
+const CONTRACT_NAME: &str = env!("CARGO_PKG_NAME");
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// Exec plugin that pays out schedules configured by the proxy.
+///
+/// Anyone (i.e. a keeper) can trigger the payments that are due,
+/// which are then executed on the proxy through `PluginExecute`.
+pub struct RecurringPayments<'a> {
+    owner: Item<'a, Addr>,
+    schedule_id: Item<'a, u64>,
+    schedules: Map<'a, u64, Schedule>,
+    /// (next payment time in nanos, schedule id) so due payments can be ranged in order
+    due_payments: Map<'a, (u64, u64), Empty>,
+    /// Schedules as they were before their payment was dispatched,
+    /// restored if the payment fails and removed once it has been replied to
+    pending_payments: Map<'a, u64, Schedule>,
+}
+
+impl Default for RecurringPayments<'_> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_points)]
+#[contract]
+#[error(RecurringPaymentsError)]
+impl RecurringPayments<'_> {
+    pub const fn new() -> Self {
+        Self {
+            owner: Item::new("owner"),
+            schedule_id: Item::new("schedule_id"),
+            schedules: Map::new("schedules"),
+            due_payments: Map::new("due_payments"),
+            pending_payments: Map::new("pending_payments"),
+        }
+    }
+
+    #[msg(instantiate)]
+    fn instantiate(
+        &self,
+        ctx: InstantiateCtx,
+        schedules: Vec<ScheduleParams>,
+    ) -> Result<Response, RecurringPaymentsError> {
+        set_contract_version(ctx.deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+        self.owner.save(ctx.deps.storage, &ctx.info.sender)?;
+        self.schedule_id.save(ctx.deps.storage, &0)?;
+
+        let mut event = self.get_event("instantiate");
+        for params in schedules {
+            let id =
+                self.save_new_schedule(ctx.deps.storage, ctx.deps.api, ctx.env.block.time, params)?;
+            event = event.add_attribute("schedule_id", id.to_string());
+        }
+
+        Ok(Response::new().add_event(event))
+    }
+
+    #[msg(migrate)]
+    fn migrate(&self, ctx: MigrateCtx) -> Result<Response, RecurringPaymentsError> {
+        set_contract_version(ctx.deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+        let event = self.get_event("migrate");
+        Ok(Response::new().add_event(event))
+    }
+
+    /// Permission: owner (proxy)
+    #[msg(exec)]
+    fn add_schedule(
+        &self,
+        ctx: ExecCtx,
+        schedule: ScheduleParams,
+    ) -> Result<Response, RecurringPaymentsError> {
+        self.ensure_owner(ctx.deps.as_ref(), &ctx.info.sender)?;
+        let id =
+            self.save_new_schedule(ctx.deps.storage, ctx.deps.api, ctx.env.block.time, schedule)?;
+
+        let event = self
+            .get_event("add_schedule")
+            .add_attribute("schedule_id", id.to_string());
+        Ok(Response::new().add_event(event))
+    }
+
+    /// Permission: owner (proxy)
+    #[msg(exec)]
+    fn cancel_schedule(&self, ctx: ExecCtx, id: u64) -> Result<Response, RecurringPaymentsError> {
+        self.ensure_owner(ctx.deps.as_ref(), &ctx.info.sender)?;
+        let schedule = self
+            .schedules
+            .may_load(ctx.deps.storage, id)?
+            .ok_or(RecurringPaymentsError::ScheduleNotFound(id))?;
+
+        self.schedules.remove(ctx.deps.storage, id);
+        self.due_payments
+            .remove(ctx.deps.storage, (schedule.next_payment.nanos(), id));
+
+        let event = self
+            .get_event("cancel_schedule")
+            .add_attribute("schedule_id", id.to_string());
+        Ok(Response::new().add_event(event))
+    }
+
+    /// Permission: Open
+    /// Pays the due payments, oldest first, up to `limit`.
+    /// If `ids` is provided, only those schedules are considered.
+    ///
+    /// Each schedule is paid at most once per call,
+    /// a schedule that missed several payments stays due until it has caught up.
+    ///
+    /// Each payment is executed on the proxy in its own submessage so one failing payment
+    /// (e.g. insufficient balance) does not block the others.
+    /// A failed payment stays due and is counted in `payments_failed`,
+    /// keepers can pass `ids` to get past schedules that keep failing.
+    /// Ids of schedules that do not exist (anymore) are skipped.
+    #[msg(exec)]
+    fn trigger_payments(
+        &self,
+        ctx: ExecCtx,
+        ids: Option<Vec<u64>>,
+        limit: Option<u32>,
+    ) -> Result<Response, RecurringPaymentsError> {
+        let now = ctx.env.block.time;
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+
+        let candidates: Vec<u64> = match ids {
+            Some(mut ids) => {
+                ids.sort_unstable();
+                ids.dedup();
+                ids.into_iter().take(limit).collect()
+            }
+            None => self
+                .due_payments
+                .keys(
+                    ctx.deps.storage,
+                    None,
+                    Some(Bound::inclusive((now.nanos(), u64::MAX))),
+                    Order::Ascending,
+                )
+                .take(limit)
+                .map(|key| key.map(|(_, id)| id))
+                .collect::<StdResult<_>>()?,
+        };
+
+        let owner = self.owner.load(ctx.deps.storage)?;
+        let mut payments: Vec<SubMsg> = vec![];
+        let mut event = self.get_event("trigger_payments");
+        for id in candidates {
+            let Some(mut schedule) = self.schedules.may_load(ctx.deps.storage, id)? else {
+                continue;
+            };
+            if schedule.next_payment > now {
+                continue;
+            }
+
+            let payment = CosmosMsg::Bank(BankMsg::Send {
+                to_address: schedule.recipient.to_string(),
+                amount: schedule.amount.clone(),
+            });
+            payments.push(SubMsg::reply_always(
+                WasmMsg::Execute {
+                    contract_addr: owner.to_string(),
+                    msg: to_binary(&VectisWalletExecMsg::PluginExecute { msg: vec![payment] })?,
+                    funds: vec![],
+                },
+                id,
+            ));
+            event = event.add_attribute("paid", id.to_string());
+
+            self.pending_payments
+                .save(ctx.deps.storage, id, &schedule)?;
+            self.due_payments
+                .remove(ctx.deps.storage, (schedule.next_payment.nanos(), id));
+            schedule.payments_made += 1;
+
+            // A schedule whose next payment cannot be represented has no payment left
+            match next_payment(schedule.next_payment, schedule.interval) {
+                Some(next) if !matches!(schedule.end, Some(end) if next >= end) => {
+                    schedule.next_payment = next;
+                    self.due_payments
+                        .save(ctx.deps.storage, (next.nanos(), id), &Empty {})?;
+                    self.schedules.save(ctx.deps.storage, id, &schedule)?;
+                }
+                _ => {
+                    self.schedules.remove(ctx.deps.storage, id);
+                    event = event.add_attribute("completed", id.to_string());
+                }
+            }
+        }
+
+        if payments.is_empty() {
+            return Err(RecurringPaymentsError::NoPaymentDue);
+        }
+
+        Ok(Response::new().add_submessages(payments).add_event(event))
+    }
+
+    /// Payments are dispatched with the schedule id as reply id,
+    /// a failed payment restores the schedule so the payment stays due
+    #[msg(reply)]
+    fn reply(&self, ctx: ReplyCtx, reply: Reply) -> Result<Response, RecurringPaymentsError> {
+        let id = reply.id;
+        let mut schedule = self.pending_payments.load(ctx.deps.storage, id)?;
+        self.pending_payments.remove(ctx.deps.storage, id);
+
+        let err = match reply.result {
+            SubMsgResult::Ok(_) => return Ok(Response::new()),
+            SubMsgResult::Err(err) => err,
+        };
+
+        // The schedule is removed if the failed payment was its last one
+        if let Some(advanced) = self.schedules.may_load(ctx.deps.storage, id)? {
+            self.due_payments
+                .remove(ctx.deps.storage, (advanced.next_payment.nanos(), id));
+        }
+        schedule.payments_failed += 1;
+        self.schedules.save(ctx.deps.storage, id, &schedule)?;
+        self.due_payments.save(
+            ctx.deps.storage,
+            (schedule.next_payment.nanos(), id),
+            &Empty {},
+        )?;
+
+        let event = self
+            .get_event("payment_failed")
+            .add_attribute("schedule_id", id.to_string())
+            .add_attribute("error", err);
+        Ok(Response::new().add_event(event))
+    }
+
+    #[msg(query)]
+    fn owner(&self, ctx: QueryCtx) -> Result<Addr, RecurringPaymentsError> {
+        Ok(self.owner.load(ctx.deps.storage)?)
+    }
+
+    #[msg(query)]
+    fn schedule(&self, ctx: QueryCtx, id: u64) -> Result<ScheduleResponse, RecurringPaymentsError> {
+        let schedule = self
+            .schedules
+            .may_load(ctx.deps.storage, id)?
+            .ok_or(RecurringPaymentsError::ScheduleNotFound(id))?;
+        Ok(ScheduleResponse { id, schedule })
+    }
+
+    #[msg(query)]
+    fn schedules(
+        &self,
+        ctx: QueryCtx,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    ) -> Result<SchedulesResponse, RecurringPaymentsError> {
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let schedules = self
+            .schedules
+            .range(
+                ctx.deps.storage,
+                start_after.map(Bound::exclusive),
+                None,
+                Order::Ascending,
+            )
+            .take(limit)
+            .map(|item| item.map(|(id, schedule)| ScheduleResponse { id, schedule }))
+            .collect::<StdResult<_>>()?;
+
+        Ok(SchedulesResponse { schedules })
+    }
+
+    /// Returns the next payment of each schedule in the order they are due,
+    /// including payments that are already due but not yet triggered.
+    /// If `until` is provided, only payments due at or before it are returned.
+    /// `start_after` is the (`due`, `schedule_id`) of the last payment of the previous page.
+    #[msg(query)]
+    fn upcoming_payments(
+        &self,
+        ctx: QueryCtx,
+        until: Option<Timestamp>,
+        start_after: Option<(Timestamp, u64)>,
+        limit: Option<u32>,
+    ) -> Result<UpcomingPaymentsResponse, RecurringPaymentsError> {
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let payments = self
+            .due_payments
+            .keys(
+                ctx.deps.storage,
+                start_after.map(|(due, id)| Bound::exclusive((due.nanos(), id))),
+                until.map(|t| Bound::inclusive((t.nanos(), u64::MAX))),
+                Order::Ascending,
+            )
+            .take(limit)
+            .map(|key| -> StdResult<UpcomingPayment> {
+                let (due, schedule_id) = key?;
+                let schedule = self.schedules.load(ctx.deps.storage, schedule_id)?;
+                Ok(UpcomingPayment {
+                    schedule_id,
+                    due: Timestamp::from_nanos(due),
+                    recipient: schedule.recipient,
+                    amount: schedule.amount,
+                })
+            })
+            .collect::<StdResult<_>>()?;
+
+        Ok(UpcomingPaymentsResponse { payments })
+    }
+
+    #[msg(query)]
+    fn contract_version(&self, ctx: QueryCtx) -> Result<ContractVersion, RecurringPaymentsError> {
+        Ok(get_contract_version(ctx.deps.storage)?)
+    }
+
+    pub(crate) fn get_event(&self, action: impl Into<String>) -> Event {
+        Event::new("vectis.recurring_payments.v1").add_attribute("action", action)
+    }
+
+    fn ensure_owner(&self, deps: Deps, sender: &Addr) -> Result<(), RecurringPaymentsError> {
+        if self.owner.load(deps.storage)? != *sender {
+            return Err(RecurringPaymentsError::Unauthorized);
+        }
+        Ok(())
+    }
+
+    fn save_new_schedule(
+        &self,
+        storage: &mut dyn Storage,
+        api: &dyn Api,
+        now: Timestamp,
+        params: ScheduleParams,
+    ) -> Result<u64, RecurringPaymentsError> {
+        let recipient = api.addr_validate(&params.recipient)?;
+        if params.amount.is_empty() || params.amount.iter().any(|c| c.amount.is_zero()) {
+            return Err(RecurringPaymentsError::InvalidSchedule(
+                "amount must not be empty or zero".into(),
+            ));
+        }
+        if params.interval == 0 {
+            return Err(RecurringPaymentsError::InvalidSchedule(
+                "interval must be greater than 0".into(),
+            ));
+        }
+        let start = params.start.unwrap_or(now);
+        if start < now {
+            return Err(RecurringPaymentsError::InvalidSchedule(
+                "start must not be in the past".into(),
+            ));
+        }
+        if next_payment(start, params.interval).is_none() {
+            return Err(RecurringPaymentsError::InvalidSchedule(
+                "interval is too large".into(),
+            ));
+        }
+        if params.end.is_some_and(|end| end <= start) {
+            return Err(RecurringPaymentsError::InvalidSchedule(
+                "end must be after start".into(),
+            ));
+        }
+
+        let id = self
+            .schedule_id
+            .update(storage, |id| -> StdResult<_> { Ok(id + 1) })?;
+        self.schedules.save(
+            storage,
+            id,
+            &Schedule {
+                recipient,
+                amount: params.amount,
+                interval: params.interval,
+                next_payment: start,
+                end: params.end,
+                payments_made: 0,
+                payments_failed: 0,
+            },
+        )?;
+        self.due_payments
+            .save(storage, (start.nanos(), id), &Empty {})?;
+
+        Ok(id)
+    }
+}
+
+/// Time of the payment following `current`, `None` if it overflows
+fn next_payment(current: Timestamp, interval: u64) -> Option<Timestamp> {
+    interval
+        .checked_mul(1_000_000_000)
+        .and_then(|nanos| current.nanos().checked_add(nanos))
+        .map(Timestamp::from_nanos)
+}
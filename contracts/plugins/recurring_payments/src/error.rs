@@ -0,0 +1,16 @@
+use cosmwasm_std::StdError;
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum RecurringPaymentsError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+    #[error("Unauthorized")]
+    Unauthorized,
+    #[error("Schedule Not Found {0}")]
+    ScheduleNotFound(u64),
+    #[error("Invalid Schedule: {0}")]
+    InvalidSchedule(String),
+    #[error("No Payment Due")]
+    NoPaymentDue,
+}
@@ -0,0 +1,57 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Addr, Coin, Timestamp};
+
+/// Input for creating a payment schedule
+#[cw_serde]
+pub struct ScheduleParams {
+    /// The address receiving the payments
+    pub recipient: String,
+    /// The amount sent on each payment
+    pub amount: Vec<Coin>,
+    /// Seconds between two payments, must be greater than 0
+    pub interval: u64,
+    /// Time of the first payment, must not be in the past, defaults to the current block time
+    pub start: Option<Timestamp>,
+    /// No payment is due at or after this time, the schedule never ends if not provided
+    pub end: Option<Timestamp>,
+}
+
+#[cw_serde]
+pub struct Schedule {
+    pub recipient: Addr,
+    pub amount: Vec<Coin>,
+    /// Seconds between two payments
+    pub interval: u64,
+    /// Time the next payment is due
+    pub next_payment: Timestamp,
+    pub end: Option<Timestamp>,
+    /// Number of payments made so far
+    pub payments_made: u64,
+    /// Number of payment attempts that failed, a failed payment stays due
+    pub payments_failed: u64,
+}
+
+#[cw_serde]
+pub struct ScheduleResponse {
+    pub id: u64,
+    pub schedule: Schedule,
+}
+
+#[cw_serde]
+pub struct SchedulesResponse {
+    pub schedules: Vec<ScheduleResponse>,
+}
+
+#[cw_serde]
+pub struct UpcomingPayment {
+    pub schedule_id: u64,
+    pub due: Timestamp,
+    pub recipient: Addr,
+    pub amount: Vec<Coin>,
+}
+
+#[cw_serde]
+pub struct UpcomingPaymentsResponse {
+    /// Next payment of each schedule, ordered by the time it is due
+    pub payments: Vec<UpcomingPayment>,
+}
@@ -0,0 +1,110 @@
+use cosmwasm_std::{Addr, CosmosMsg, Event, Response, StdError};
+use cw2::{get_contract_version, set_contract_version, ContractVersion};
+use cw_storage_plus::Item;
+use sylvia::{
+    contract, schemars,
+    types::{InstantiateCtx, MigrateCtx, QueryCtx},
+};
+
+// Vectis lib
+use vectis_spending_limit_hook::contract::sv::QueryMsg as SpendingLimitHookQueryMsg;
+use vectis_wallet::interface::{pre_tx_check_trait, PreTxCheckTrait};
+
+use crate::error::SpendingLimitCheckError;
+
+#[cfg(not(feature = "library"))]
+use sylvia::entry_points;
+
+const CONTRACT_NAME: &str = env!("CARGO_PKG_NAME");
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// PreTxCheck plugin that enforces the limits of a `vectis-spending-limit-hook`.
+///
+/// The hook has to be installed on the same proxy first,
+/// this plugin only forwards the check to it as the hook holds the limits and recorded spend.
+/// Removing the hook while this plugin is installed fails every check.
+pub struct SpendingLimitCheck<'a> {
+    hook: Item<'a, Addr>,
+}
+
+impl Default for SpendingLimitCheck<'_> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+mod pretxchecktrait {
+    use super::*;
+
+    #[contract(module=crate::contract)]
+    #[messages(pre_tx_check_trait as PreTxCheckTrait)]
+    #[error(SpendingLimitCheckError)]
+    impl PreTxCheckTrait for SpendingLimitCheck<'_> {
+        type Error = SpendingLimitCheckError;
+
+        #[msg(query)]
+        fn pre_tx_check(&self, ctx: QueryCtx, msgs: Vec<CosmosMsg>) -> Result<bool, Self::Error> {
+            let hook = self.hook.load(ctx.deps.storage)?;
+            Ok(ctx
+                .deps
+                .querier
+                .query_wasm_smart(hook, &SpendingLimitHookQueryMsg::CheckMsgs { msgs })?)
+        }
+
+        #[msg(query)]
+        fn contract_version(&self, ctx: QueryCtx) -> Result<ContractVersion, StdError> {
+            get_contract_version(ctx.deps.storage)
+        }
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_points)]
+#[contract]
+#[error(SpendingLimitCheckError)]
+#[messages(pre_tx_check_trait as PreTxCheckTrait)]
+impl SpendingLimitCheck<'_> {
+    pub const fn new() -> Self {
+        Self {
+            hook: Item::new("hook"),
+        }
+    }
+
+    /// `hook` must be owned by the proxy installing this plugin
+    #[msg(instantiate)]
+    fn instantiate(
+        &self,
+        ctx: InstantiateCtx,
+        hook: String,
+    ) -> Result<Response, SpendingLimitCheckError> {
+        set_contract_version(ctx.deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+        let hook = ctx.deps.api.addr_validate(&hook)?;
+        let hook_owner: Addr = ctx
+            .deps
+            .querier
+            .query_wasm_smart(&hook, &SpendingLimitHookQueryMsg::Owner {})?;
+        if hook_owner != ctx.info.sender {
+            return Err(SpendingLimitCheckError::Unauthorized);
+        }
+        self.hook.save(ctx.deps.storage, &hook)?;
+
+        let event = self.get_event("instantiate").add_attribute("hook", hook);
+        Ok(Response::new().add_event(event))
+    }
+
+    #[msg(migrate)]
+    fn migrate(&self, ctx: MigrateCtx) -> Result<Response, SpendingLimitCheckError> {
+        set_contract_version(ctx.deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+        let event = self.get_event("migrate");
+        Ok(Response::new().add_event(event))
+    }
+
+    #[msg(query)]
+    fn hook(&self, ctx: QueryCtx) -> Result<Addr, SpendingLimitCheckError> {
+        Ok(self.hook.load(ctx.deps.storage)?)
+    }
+
+    pub(crate) fn get_event(&self, action: impl Into<String>) -> Event {
+        Event::new("vectis.spending_limit_check.v1").add_attribute("action", action)
+    }
+}
@@ -0,0 +1,3 @@
+#![allow(deprecated)]
+pub mod contract;
+pub mod error;
@@ -0,0 +1,359 @@
+use std::collections::BTreeMap;
+
+use cosmwasm_std::{
+    Addr, BankMsg, CosmosMsg, Deps, Event, IbcMsg, Order, Response, StdError, StdResult, Storage,
+    Timestamp, Uint128, WasmMsg,
+};
+use cw2::{get_contract_version, set_contract_version, ContractVersion};
+use cw_storage_plus::{Bound, Item, Map};
+use sylvia::{
+    contract, schemars,
+    types::{ExecCtx, InstantiateCtx, MigrateCtx, QueryCtx},
+};
+
+// Vectis lib
+use vectis_wallet::{
+    interface::{post_tx_hook_trait, PostTxHookTrait},
+    DEFAULT_LIMIT, MAX_LIMIT,
+};
+
+use crate::{
+    error::SpendingLimitError,
+    types::{Limit, LimitParams, LimitResponse, LimitsResponse},
+};
+
+#[cfg(not(feature = "library"))]
+use sylvia::entry_points;
+
+const CONTRACT_NAME: &str = env!("CARGO_PKG_NAME");
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// PostTxHook plugin that records what the proxy spends against rolling per-denom limits.
+///
+/// `pre_tx_check` is a query and cannot write state,
+/// so the limits are enforced by `vectis-spending-limit-check`,
+/// which asks this contract through the `check_msgs` query.
+/// Stargate msgs cannot be decoded, so they are rejected while any limit is set.
+pub struct SpendingLimitHook<'a> {
+    owner: Item<'a, Addr>,
+    limits: Map<'a, &'a str, Limit>,
+    /// (denom, block time in nanos) -> amount spent in that block
+    spends: Map<'a, (&'a str, u64), Uint128>,
+}
+
+impl Default for SpendingLimitHook<'_> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+mod posttxhooktrait {
+    use super::*;
+
+    #[contract(module=crate::contract)]
+    #[messages(post_tx_hook_trait as PostTxHookTrait)]
+    #[error(SpendingLimitError)]
+    impl PostTxHookTrait for SpendingLimitHook<'_> {
+        type Error = SpendingLimitError;
+
+        /// Permission: owner (proxy)
+        /// Records the spend of the limited denoms, expired records of those denoms are pruned
+        #[msg(exec)]
+        fn post_tx_hook(
+            &self,
+            ctx: ExecCtx,
+            msgs: Vec<CosmosMsg>,
+        ) -> Result<Response, Self::Error> {
+            self.ensure_owner(ctx.deps.as_ref(), &ctx.info.sender)?;
+            let now = ctx.env.block.time;
+
+            let mut event = self.get_event("post_tx_hook");
+            for (denom, amount) in msgs_spend(&msgs)? {
+                let limit = match self.limits.may_load(ctx.deps.storage, &denom)? {
+                    Some(limit) => limit,
+                    None => continue,
+                };
+
+                self.prune_spends(ctx.deps.storage, &denom, window_start(now, limit.period))?;
+                self.spends.update(
+                    ctx.deps.storage,
+                    (&denom, now.nanos()),
+                    |spent| -> Result<_, SpendingLimitError> {
+                        Ok(spent.unwrap_or_default().checked_add(amount)?)
+                    },
+                )?;
+                event = event.add_attribute("spent", format!("{amount}{denom}"));
+            }
+
+            Ok(Response::new().add_event(event))
+        }
+
+        #[msg(query)]
+        fn contract_version(&self, ctx: QueryCtx) -> Result<ContractVersion, StdError> {
+            get_contract_version(ctx.deps.storage)
+        }
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_points)]
+#[contract]
+#[error(SpendingLimitError)]
+#[messages(post_tx_hook_trait as PostTxHookTrait)]
+impl SpendingLimitHook<'_> {
+    pub const fn new() -> Self {
+        Self {
+            owner: Item::new("owner"),
+            limits: Map::new("limits"),
+            spends: Map::new("spends"),
+        }
+    }
+
+    #[msg(instantiate)]
+    fn instantiate(
+        &self,
+        ctx: InstantiateCtx,
+        limits: Vec<LimitParams>,
+    ) -> Result<Response, SpendingLimitError> {
+        set_contract_version(ctx.deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+        self.owner.save(ctx.deps.storage, &ctx.info.sender)?;
+
+        let mut event = self.get_event("instantiate");
+        for params in limits {
+            event = event.add_attribute("denom", params.denom.clone());
+            self.save_limit(ctx.deps.storage, params)?;
+        }
+
+        Ok(Response::new().add_event(event))
+    }
+
+    #[msg(migrate)]
+    fn migrate(&self, ctx: MigrateCtx) -> Result<Response, SpendingLimitError> {
+        set_contract_version(ctx.deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+        let event = self.get_event("migrate");
+        Ok(Response::new().add_event(event))
+    }
+
+    /// Permission: owner (proxy)
+    /// Sets or replaces the limit of a denom, the spend in the current window is kept.
+    /// Spend older than the previous `period` has already been pruned,
+    /// so a longer period starts with only the spend of the old window
+    #[msg(exec)]
+    fn set_limit(&self, ctx: ExecCtx, limit: LimitParams) -> Result<Response, SpendingLimitError> {
+        self.ensure_owner(ctx.deps.as_ref(), &ctx.info.sender)?;
+        let event = self
+            .get_event("set_limit")
+            .add_attribute("denom", limit.denom.clone());
+        self.save_limit(ctx.deps.storage, limit)?;
+        Ok(Response::new().add_event(event))
+    }
+
+    /// Permission: owner (proxy)
+    #[msg(exec)]
+    fn remove_limit(&self, ctx: ExecCtx, denom: String) -> Result<Response, SpendingLimitError> {
+        self.ensure_owner(ctx.deps.as_ref(), &ctx.info.sender)?;
+        if !self.limits.has(ctx.deps.storage, &denom) {
+            return Err(SpendingLimitError::LimitNotFound(denom));
+        }
+
+        self.limits.remove(ctx.deps.storage, &denom);
+        self.prune_spends(ctx.deps.storage, &denom, u64::MAX)?;
+
+        let event = self.get_event("remove_limit").add_attribute("denom", denom);
+        Ok(Response::new().add_event(event))
+    }
+
+    #[msg(query)]
+    fn owner(&self, ctx: QueryCtx) -> Result<Addr, SpendingLimitError> {
+        Ok(self.owner.load(ctx.deps.storage)?)
+    }
+
+    #[msg(query)]
+    fn limit(&self, ctx: QueryCtx, denom: String) -> Result<LimitResponse, SpendingLimitError> {
+        let limit = self
+            .limits
+            .may_load(ctx.deps.storage, &denom)?
+            .ok_or_else(|| SpendingLimitError::LimitNotFound(denom.clone()))?;
+        self.limit_response(ctx.deps.storage, ctx.env.block.time, denom, limit)
+    }
+
+    #[msg(query)]
+    fn limits(
+        &self,
+        ctx: QueryCtx,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> Result<LimitsResponse, SpendingLimitError> {
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let limits = self
+            .limits
+            .range(
+                ctx.deps.storage,
+                start_after.as_deref().map(Bound::exclusive),
+                None,
+                Order::Ascending,
+            )
+            .take(limit)
+            .collect::<StdResult<Vec<_>>>()?;
+
+        let limits = limits
+            .into_iter()
+            .map(|(denom, limit)| {
+                self.limit_response(ctx.deps.storage, ctx.env.block.time, denom, limit)
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(LimitsResponse { limits })
+    }
+
+    /// Returns `false` if executing `msgs` now would exceed any of the limits.
+    ///
+    /// Stargate msgs (e.g. `MsgSend`, `MsgTransfer`) can move funds without being counted,
+    /// so `false` is also returned for them while any limit is set
+    #[msg(query)]
+    fn check_msgs(&self, ctx: QueryCtx, msgs: Vec<CosmosMsg>) -> Result<bool, SpendingLimitError> {
+        if msgs
+            .iter()
+            .any(|msg| matches!(msg, CosmosMsg::Stargate { .. }))
+            && !self.limits.is_empty(ctx.deps.storage)
+        {
+            return Ok(false);
+        }
+
+        let now = ctx.env.block.time;
+        for (denom, amount) in msgs_spend(&msgs)? {
+            if let Some(limit) = self.limits.may_load(ctx.deps.storage, &denom)? {
+                let spent =
+                    self.spent(ctx.deps.storage, &denom, window_start(now, limit.period))?;
+                if spent.checked_add(amount)? > limit.amount {
+                    return Ok(false);
+                }
+            }
+        }
+        Ok(true)
+    }
+
+    pub(crate) fn get_event(&self, action: impl Into<String>) -> Event {
+        Event::new("vectis.spending_limit_hook.v1").add_attribute("action", action)
+    }
+
+    fn ensure_owner(&self, deps: Deps, sender: &Addr) -> Result<(), SpendingLimitError> {
+        if self.owner.load(deps.storage)? != *sender {
+            return Err(SpendingLimitError::Unauthorized);
+        }
+        Ok(())
+    }
+
+    fn save_limit(
+        &self,
+        storage: &mut dyn Storage,
+        params: LimitParams,
+    ) -> Result<(), SpendingLimitError> {
+        if params.denom.is_empty() {
+            return Err(SpendingLimitError::InvalidLimit(
+                "denom must not be empty".into(),
+            ));
+        }
+        if params.period == 0 {
+            return Err(SpendingLimitError::InvalidLimit(
+                "period must be greater than 0".into(),
+            ));
+        }
+
+        self.limits.save(
+            storage,
+            &params.denom,
+            &Limit {
+                amount: params.amount,
+                period: params.period,
+            },
+        )?;
+        Ok(())
+    }
+
+    /// Sum of the spend recorded after `since` (in nanos)
+    fn spent(
+        &self,
+        storage: &dyn Storage,
+        denom: &str,
+        since: u64,
+    ) -> Result<Uint128, SpendingLimitError> {
+        self.spends
+            .prefix(denom)
+            .range(
+                storage,
+                Some(Bound::exclusive(since)),
+                None,
+                Order::Ascending,
+            )
+            .try_fold(Uint128::zero(), |total, item| {
+                Ok(total.checked_add(item?.1)?)
+            })
+    }
+
+    /// Removes the spend recorded at or before `until` (in nanos)
+    fn prune_spends(
+        &self,
+        storage: &mut dyn Storage,
+        denom: &str,
+        until: u64,
+    ) -> Result<(), SpendingLimitError> {
+        let expired = self
+            .spends
+            .prefix(denom)
+            .keys(
+                storage,
+                None,
+                Some(Bound::inclusive(until)),
+                Order::Ascending,
+            )
+            .collect::<StdResult<Vec<_>>>()?;
+        for time in expired {
+            self.spends.remove(storage, (denom, time));
+        }
+        Ok(())
+    }
+
+    fn limit_response(
+        &self,
+        storage: &dyn Storage,
+        now: Timestamp,
+        denom: String,
+        limit: Limit,
+    ) -> Result<LimitResponse, SpendingLimitError> {
+        let spent = self.spent(storage, &denom, window_start(now, limit.period))?;
+        Ok(LimitResponse {
+            remaining: limit.amount.saturating_sub(spent),
+            denom,
+            limit,
+            spent,
+        })
+    }
+}
+
+/// Spend recorded at or before the returned time (in nanos) is outside of the window
+fn window_start(now: Timestamp, period: u64) -> u64 {
+    now.nanos()
+        .saturating_sub(period.saturating_mul(1_000_000_000))
+}
+
+/// Total amount per denom sent out of the proxy by `msgs`,
+/// i.e. bank sends and burns, funds attached to contract calls and IBC transfers
+fn msgs_spend(msgs: &[CosmosMsg]) -> Result<BTreeMap<String, Uint128>, SpendingLimitError> {
+    let mut spend: BTreeMap<String, Uint128> = BTreeMap::new();
+    for msg in msgs {
+        let coins = match msg {
+            CosmosMsg::Bank(BankMsg::Send { amount, .. }) => amount.clone(),
+            CosmosMsg::Bank(BankMsg::Burn { amount }) => amount.clone(),
+            CosmosMsg::Wasm(WasmMsg::Execute { funds, .. }) => funds.clone(),
+            CosmosMsg::Wasm(WasmMsg::Instantiate { funds, .. }) => funds.clone(),
+            CosmosMsg::Wasm(WasmMsg::Instantiate2 { funds, .. }) => funds.clone(),
+            CosmosMsg::Ibc(IbcMsg::Transfer { amount, .. }) => vec![amount.clone()],
+            _ => vec![],
+        };
+        for coin in coins {
+            let total = spend.entry(coin.denom).or_default();
+            *total = total.checked_add(coin.amount)?;
+        }
+    }
+    Ok(spend)
+}
@@ -0,0 +1,16 @@
+use cosmwasm_std::{OverflowError, StdError};
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum SpendingLimitError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+    #[error("{0}")]
+    Overflow(#[from] OverflowError),
+    #[error("Unauthorized")]
+    Unauthorized,
+    #[error("Limit Not Found {0}")]
+    LimitNotFound(String),
+    #[error("Invalid Limit: {0}")]
+    InvalidLimit(String),
+}
@@ -0,0 +1,4 @@
+#![allow(deprecated)]
+pub mod contract;
+pub mod error;
+pub mod types;
@@ -0,0 +1,34 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::Uint128;
+
+/// Input for setting the spending limit of a denom
+#[cw_serde]
+pub struct LimitParams {
+    pub denom: String,
+    /// The maximum amount that can be spent within any `period`
+    pub amount: Uint128,
+    /// Length of the rolling window in seconds, must be greater than 0
+    pub period: u64,
+}
+
+#[cw_serde]
+pub struct Limit {
+    pub amount: Uint128,
+    /// Length of the rolling window in seconds
+    pub period: u64,
+}
+
+#[cw_serde]
+pub struct LimitResponse {
+    pub denom: String,
+    pub limit: Limit,
+    /// Amount spent within the current window
+    pub spent: Uint128,
+    /// Amount that can still be spent within the current window
+    pub remaining: Uint128,
+}
+
+#[cw_serde]
+pub struct LimitsResponse {
+    pub limits: Vec<LimitResponse>,
+}
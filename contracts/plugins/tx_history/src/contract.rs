@@ -0,0 +1,427 @@
+use cosmwasm_std::{
+    Addr, BankMsg, CosmosMsg, Deps, DistributionMsg, Empty, Event, IbcMsg, Order, Response,
+    StakingMsg, StdError, StdResult, Storage, Timestamp, WasmMsg,
+};
+use cw2::{get_contract_version, set_contract_version, ContractVersion};
+use cw_storage_plus::{Bound, Item, Map};
+use sylvia::{
+    contract, schemars,
+    types::{ExecCtx, InstantiateCtx, MigrateCtx, QueryCtx},
+};
+
+// Vectis lib
+use vectis_wallet::{
+    interface::{post_tx_hook_trait, PostTxHookTrait},
+    DEFAULT_LIMIT, MAX_LIMIT,
+};
+
+use crate::{
+    error::TxHistoryError,
+    types::{MsgKind, MsgSummary, Retention, TxRecord, TxRecordResponse, TxRecordsResponse},
+};
+
+#[cfg(not(feature = "library"))]
+use sylvia::entry_points;
+
+const CONTRACT_NAME: &str = env!("CARGO_PKG_NAME");
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// Maximum number of records pruned in a single hook so the gas used stays bounded
+pub const MAX_PRUNE_PER_TX: usize = 10;
+
+/// PostTxHook plugin that keeps the history of the txs executed by the proxy.
+///
+/// Records are pruned oldest first according to the retention.
+pub struct TxHistory<'a> {
+    owner: Item<'a, Addr>,
+    retention: Item<'a, Retention>,
+    record_id: Item<'a, u64>,
+    /// Number of records currently stored
+    record_count: Item<'a, u32>,
+    records: Map<'a, u64, TxRecord>,
+    /// (time in nanos, record id), to find the first record of a time range
+    by_time: Map<'a, (u64, u64), Empty>,
+    /// (counterparty, record id)
+    by_counterparty: Map<'a, (&'a str, u64), Empty>,
+}
+
+impl Default for TxHistory<'_> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+mod posttxhooktrait {
+    use super::*;
+
+    #[contract(module=crate::contract)]
+    #[messages(post_tx_hook_trait as PostTxHookTrait)]
+    #[error(TxHistoryError)]
+    impl PostTxHookTrait for TxHistory<'_> {
+        type Error = TxHistoryError;
+
+        /// Permission: owner (proxy)
+        #[msg(exec)]
+        fn post_tx_hook(
+            &self,
+            ctx: ExecCtx,
+            msgs: Vec<CosmosMsg>,
+        ) -> Result<Response, Self::Error> {
+            self.ensure_owner(ctx.deps.as_ref(), &ctx.info.sender)?;
+
+            let record = TxRecord {
+                height: ctx.env.block.height,
+                time: ctx.env.block.time,
+                msgs: msgs.iter().map(summarise).collect(),
+            };
+            let id = self
+                .record_id
+                .update(ctx.deps.storage, |id| -> StdResult<_> { Ok(id + 1) })?;
+            self.records.save(ctx.deps.storage, id, &record)?;
+            self.by_time
+                .save(ctx.deps.storage, (record.time.nanos(), id), &Empty {})?;
+            for counterparty in counterparties(&record) {
+                self.by_counterparty
+                    .save(ctx.deps.storage, (&counterparty, id), &Empty {})?;
+            }
+            self.record_count
+                .update(ctx.deps.storage, |count| -> StdResult<_> { Ok(count + 1) })?;
+
+            let pruned = self.prune(ctx.deps.storage, ctx.env.block.time)?;
+
+            let event = self
+                .get_event("post_tx_hook")
+                .add_attribute("record_id", id.to_string())
+                .add_attribute("pruned", pruned.to_string());
+            Ok(Response::new().add_event(event))
+        }
+
+        #[msg(query)]
+        fn contract_version(&self, ctx: QueryCtx) -> Result<ContractVersion, StdError> {
+            get_contract_version(ctx.deps.storage)
+        }
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_points)]
+#[contract]
+#[error(TxHistoryError)]
+#[messages(post_tx_hook_trait as PostTxHookTrait)]
+impl TxHistory<'_> {
+    pub const fn new() -> Self {
+        Self {
+            owner: Item::new("owner"),
+            retention: Item::new("retention"),
+            record_id: Item::new("record_id"),
+            record_count: Item::new("record_count"),
+            records: Map::new("records"),
+            by_time: Map::new("by_time"),
+            by_counterparty: Map::new("by_counterparty"),
+        }
+    }
+
+    #[msg(instantiate)]
+    fn instantiate(
+        &self,
+        ctx: InstantiateCtx,
+        retention: Retention,
+    ) -> Result<Response, TxHistoryError> {
+        set_contract_version(ctx.deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+        validate_retention(&retention)?;
+        self.owner.save(ctx.deps.storage, &ctx.info.sender)?;
+        self.retention.save(ctx.deps.storage, &retention)?;
+        self.record_id.save(ctx.deps.storage, &0)?;
+        self.record_count.save(ctx.deps.storage, &0)?;
+
+        let event = self.get_event("instantiate");
+        Ok(Response::new().add_event(event))
+    }
+
+    #[msg(migrate)]
+    fn migrate(&self, ctx: MigrateCtx) -> Result<Response, TxHistoryError> {
+        set_contract_version(ctx.deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+        let event = self.get_event("migrate");
+        Ok(Response::new().add_event(event))
+    }
+
+    /// Permission: owner (proxy)
+    /// Records outside of the new retention are pruned on the following txs
+    #[msg(exec)]
+    fn update_retention(
+        &self,
+        ctx: ExecCtx,
+        retention: Retention,
+    ) -> Result<Response, TxHistoryError> {
+        self.ensure_owner(ctx.deps.as_ref(), &ctx.info.sender)?;
+        validate_retention(&retention)?;
+        self.retention.save(ctx.deps.storage, &retention)?;
+
+        let event = self.get_event("update_retention");
+        Ok(Response::new().add_event(event))
+    }
+
+    #[msg(query)]
+    fn owner(&self, ctx: QueryCtx) -> Result<Addr, TxHistoryError> {
+        Ok(self.owner.load(ctx.deps.storage)?)
+    }
+
+    #[msg(query)]
+    fn retention(&self, ctx: QueryCtx) -> Result<Retention, TxHistoryError> {
+        Ok(self.retention.load(ctx.deps.storage)?)
+    }
+
+    #[msg(query)]
+    fn record(&self, ctx: QueryCtx, id: u64) -> Result<TxRecordResponse, TxHistoryError> {
+        let record = self
+            .records
+            .may_load(ctx.deps.storage, id)?
+            .ok_or(TxHistoryError::RecordNotFound(id))?;
+        Ok(TxRecordResponse { id, record })
+    }
+
+    /// Returns the records newest first
+    #[msg(query)]
+    fn history(
+        &self,
+        ctx: QueryCtx,
+        start_before: Option<u64>,
+        limit: Option<u32>,
+    ) -> Result<TxRecordsResponse, TxHistoryError> {
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let records = self
+            .records
+            .range(
+                ctx.deps.storage,
+                None,
+                start_before.map(Bound::exclusive),
+                Order::Descending,
+            )
+            .take(limit)
+            .map(|item| item.map(|(id, record)| TxRecordResponse { id, record }))
+            .collect::<StdResult<_>>()?;
+
+        Ok(TxRecordsResponse { records })
+    }
+
+    /// Returns the records executed between `from` and `to` (inclusive) oldest first
+    #[msg(query)]
+    fn history_by_time(
+        &self,
+        ctx: QueryCtx,
+        from: Option<Timestamp>,
+        to: Option<Timestamp>,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    ) -> Result<TxRecordsResponse, TxHistoryError> {
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+
+        // Record ids increase with time, so the range starts from the first id at or after `from`
+        let first_id = match from {
+            Some(from) => self
+                .by_time
+                .keys(
+                    ctx.deps.storage,
+                    Some(Bound::inclusive((from.nanos(), 0))),
+                    None,
+                    Order::Ascending,
+                )
+                .next()
+                .transpose()?
+                .map(|(_, id)| id),
+            None => Some(0),
+        };
+        let first_id = match (first_id, start_after) {
+            (Some(first_id), Some(start_after)) => first_id.max(start_after.saturating_add(1)),
+            (Some(first_id), None) => first_id,
+            (None, _) => return Ok(TxRecordsResponse { records: vec![] }),
+        };
+
+        let mut records = vec![];
+        for item in self
+            .records
+            .range(
+                ctx.deps.storage,
+                Some(Bound::inclusive(first_id)),
+                None,
+                Order::Ascending,
+            )
+            .take(limit)
+        {
+            let (id, record) = item?;
+            if to.is_some_and(|to| record.time > to) {
+                break;
+            }
+            records.push(TxRecordResponse { id, record });
+        }
+
+        Ok(TxRecordsResponse { records })
+    }
+
+    /// Returns the records with msgs to `counterparty` newest first,
+    /// counterparties are matched case insensitively
+    #[msg(query)]
+    fn history_by_counterparty(
+        &self,
+        ctx: QueryCtx,
+        counterparty: String,
+        start_before: Option<u64>,
+        limit: Option<u32>,
+    ) -> Result<TxRecordsResponse, TxHistoryError> {
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let records = self
+            .by_counterparty
+            .prefix(&counterparty.to_lowercase())
+            .keys(
+                ctx.deps.storage,
+                None,
+                start_before.map(Bound::exclusive),
+                Order::Descending,
+            )
+            .take(limit)
+            .map(|id| -> StdResult<TxRecordResponse> {
+                let id = id?;
+                let record = self.records.load(ctx.deps.storage, id)?;
+                Ok(TxRecordResponse { id, record })
+            })
+            .collect::<StdResult<_>>()?;
+
+        Ok(TxRecordsResponse { records })
+    }
+
+    pub(crate) fn get_event(&self, action: impl Into<String>) -> Event {
+        Event::new("vectis.tx_history.v1").add_attribute("action", action)
+    }
+
+    fn ensure_owner(&self, deps: Deps, sender: &Addr) -> Result<(), TxHistoryError> {
+        if self.owner.load(deps.storage)? != *sender {
+            return Err(TxHistoryError::Unauthorized);
+        }
+        Ok(())
+    }
+
+    /// Removes the oldest records outside of the retention, up to `MAX_PRUNE_PER_TX`
+    fn prune(&self, storage: &mut dyn Storage, now: Timestamp) -> Result<u32, TxHistoryError> {
+        let retention = self.retention.load(storage)?;
+        let mut count = self.record_count.load(storage)?;
+        let mut pruned = 0;
+
+        for _ in 0..MAX_PRUNE_PER_TX {
+            let (id, record) = match self
+                .records
+                .range(storage, None, None, Order::Ascending)
+                .next()
+                .transpose()?
+            {
+                Some(oldest) => oldest,
+                None => break,
+            };
+
+            let expired = retention.max_age.is_some_and(|max_age| {
+                now.seconds().saturating_sub(record.time.seconds()) >= max_age
+            });
+            if count <= retention.max_records && !expired {
+                break;
+            }
+
+            self.records.remove(storage, id);
+            self.by_time.remove(storage, (record.time.nanos(), id));
+            for counterparty in counterparties(&record) {
+                self.by_counterparty.remove(storage, (&counterparty, id));
+            }
+            count -= 1;
+            pruned += 1;
+        }
+
+        self.record_count.save(storage, &count)?;
+        Ok(pruned)
+    }
+}
+
+fn validate_retention(retention: &Retention) -> Result<(), TxHistoryError> {
+    if retention.max_records == 0 {
+        return Err(TxHistoryError::InvalidRetention(
+            "max_records must be greater than 0".into(),
+        ));
+    }
+    if retention.max_age == Some(0) {
+        return Err(TxHistoryError::InvalidRetention(
+            "max_age must be greater than 0".into(),
+        ));
+    }
+    Ok(())
+}
+
+/// The unique counterparties of the msgs in `record`, lowercased
+/// as the SDK accepts uppercase bech32 for the same account
+fn counterparties(record: &TxRecord) -> Vec<String> {
+    let mut counterparties: Vec<String> = record
+        .msgs
+        .iter()
+        .filter_map(|msg| msg.counterparty.as_deref().map(str::to_lowercase))
+        .collect();
+    counterparties.sort_unstable();
+    counterparties.dedup();
+    counterparties
+}
+
+fn summarise(msg: &CosmosMsg) -> MsgSummary {
+    let (kind, counterparty, funds) = match msg {
+        CosmosMsg::Bank(BankMsg::Send { to_address, amount }) => {
+            (MsgKind::BankSend, Some(to_address.clone()), amount.clone())
+        }
+        CosmosMsg::Bank(BankMsg::Burn { amount }) => (MsgKind::BankBurn, None, amount.clone()),
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr,
+            funds,
+            ..
+        }) => (
+            MsgKind::WasmExecute,
+            Some(contract_addr.clone()),
+            funds.clone(),
+        ),
+        CosmosMsg::Wasm(WasmMsg::Instantiate { funds, .. })
+        | CosmosMsg::Wasm(WasmMsg::Instantiate2 { funds, .. }) => {
+            (MsgKind::WasmInstantiate, None, funds.clone())
+        }
+        CosmosMsg::Wasm(WasmMsg::Migrate { contract_addr, .. }) => {
+            (MsgKind::WasmMigrate, Some(contract_addr.clone()), vec![])
+        }
+        CosmosMsg::Wasm(WasmMsg::UpdateAdmin { contract_addr, .. })
+        | CosmosMsg::Wasm(WasmMsg::ClearAdmin { contract_addr }) => {
+            (MsgKind::WasmAdmin, Some(contract_addr.clone()), vec![])
+        }
+        CosmosMsg::Ibc(IbcMsg::Transfer {
+            to_address, amount, ..
+        }) => (
+            MsgKind::IbcTransfer,
+            Some(to_address.clone()),
+            vec![amount.clone()],
+        ),
+        CosmosMsg::Staking(StakingMsg::Delegate { validator, amount }) => (
+            MsgKind::StakingDelegate,
+            Some(validator.clone()),
+            vec![amount.clone()],
+        ),
+        CosmosMsg::Staking(StakingMsg::Undelegate { validator, .. }) => {
+            (MsgKind::StakingUndelegate, Some(validator.clone()), vec![])
+        }
+        CosmosMsg::Staking(StakingMsg::Redelegate { dst_validator, .. }) => (
+            MsgKind::StakingRedelegate,
+            Some(dst_validator.clone()),
+            vec![],
+        ),
+        CosmosMsg::Distribution(DistributionMsg::SetWithdrawAddress { address }) => {
+            (MsgKind::Distribution, Some(address.clone()), vec![])
+        }
+        CosmosMsg::Distribution(DistributionMsg::WithdrawDelegatorReward { validator }) => {
+            (MsgKind::Distribution, Some(validator.clone()), vec![])
+        }
+        _ => (MsgKind::Other, None, vec![]),
+    };
+
+    MsgSummary {
+        kind,
+        counterparty,
+        funds,
+    }
+}
@@ -0,0 +1,14 @@
+use cosmwasm_std::StdError;
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum TxHistoryError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+    #[error("Unauthorized")]
+    Unauthorized,
+    #[error("Record Not Found {0}")]
+    RecordNotFound(u64),
+    #[error("Invalid Retention: {0}")]
+    InvalidRetention(String),
+}
@@ -0,0 +1,56 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Coin, Timestamp};
+
+/// Bounds the number of records kept, the oldest records are pruned first
+#[cw_serde]
+pub struct Retention {
+    /// Maximum number of records kept, must be greater than 0
+    pub max_records: u32,
+    /// Records older than this (in seconds) are pruned, must be greater than 0 if provided
+    pub max_age: Option<u64>,
+}
+
+#[cw_serde]
+pub enum MsgKind {
+    BankSend,
+    BankBurn,
+    WasmExecute,
+    WasmInstantiate,
+    WasmMigrate,
+    WasmAdmin,
+    IbcTransfer,
+    StakingDelegate,
+    StakingUndelegate,
+    StakingRedelegate,
+    Distribution,
+    Other,
+}
+
+/// Summary of a msg executed by the proxy
+#[cw_serde]
+pub struct MsgSummary {
+    pub kind: MsgKind,
+    /// The address receiving or called by the msg, if any
+    pub counterparty: Option<String>,
+    /// Funds moved out of the proxy by the msg
+    pub funds: Vec<Coin>,
+}
+
+/// A batch of msgs executed by the proxy in a tx
+#[cw_serde]
+pub struct TxRecord {
+    pub height: u64,
+    pub time: Timestamp,
+    pub msgs: Vec<MsgSummary>,
+}
+
+#[cw_serde]
+pub struct TxRecordResponse {
+    pub id: u64,
+    pub record: TxRecord,
+}
+
+#[cw_serde]
+pub struct TxRecordsResponse {
+    pub records: Vec<TxRecordResponse>,
+}
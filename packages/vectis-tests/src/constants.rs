@@ -102,6 +102,19 @@ lazy_static! {
     pub static ref PRE_TX_CODE_PATH: String = code_path("test_vectis_pre_tx");
     pub static ref POST_TX_CODE_PATH: String = code_path("test_vectis_post_tx_exec");
     pub static ref PLUGIN_EXEC_CODE_PATH: String = code_path("test_vectis_plugin_exec");
+
+    // Vectis reference plugin contracts
+    pub static ref RECURRING_PAYMENTS_CODE_PATH: String = code_path("vectis_recurring_payments");
+    pub static ref RECURRING_PAYMENTS_HASH: String = code_hash("vectis_recurring_payments");
+    pub static ref SPENDING_LIMIT_HOOK_CODE_PATH: String = code_path("vectis_spending_limit_hook");
+    pub static ref SPENDING_LIMIT_HOOK_HASH: String = code_hash("vectis_spending_limit_hook");
+    pub static ref SPENDING_LIMIT_CHECK_CODE_PATH: String = code_path("vectis_spending_limit_check");
+    pub static ref SPENDING_LIMIT_CHECK_HASH: String = code_hash("vectis_spending_limit_check");
+    pub static ref COMPLIANCE_CODE_PATH: String = code_path("vectis_compliance");
+    pub static ref COMPLIANCE_HASH: String = code_hash("vectis_compliance");
+    pub static ref COMPLIANCE_DENYLIST_CODE_PATH: String = code_path("vectis_compliance_denylist");
+    pub static ref TX_HISTORY_CODE_PATH: String = code_path("vectis_tx_history");
+    pub static ref TX_HISTORY_HASH: String = code_hash("vectis_tx_history");
 }
 
 /// Version of vectis
@@ -2,6 +2,7 @@
 pub mod factory_services;
 pub mod plugin_registry_mgmt;
 pub mod plugin_services;
+pub mod plugins;
 pub mod test_env;
 pub mod util;
 pub mod wallet_plugin;
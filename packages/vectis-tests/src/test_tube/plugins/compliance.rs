@@ -0,0 +1,118 @@
+use cosmwasm_std::{coin, to_binary, BankMsg, CosmosMsg, Empty};
+use osmosis_test_tube::{Account, OsmosisTestApp};
+use serial_test::serial;
+
+use vectis_compliance::{
+    contract::sv::{InstantiateMsg, QueryMsg},
+    types::{AddressStatusResponse, BlockReason},
+};
+use vectis_compliance_denylist::{
+    contract::sv::{ExecMsg as DenylistExecMsg, InstantiateMsg as DenylistInstantiateMsg},
+    types::DenylistEntry,
+};
+use vectis_wallet::types::plugin::PluginPermission;
+
+use crate::{
+    constants::*,
+    test_tube::{
+        test_env::HubChainSuite,
+        util::{
+            contract::Contract,
+            wallet::{create_webauthn_wallet, register_and_install_plugin, sign_and_submit},
+        },
+    },
+};
+
+const IOPERATOR: usize = 3;
+
+fn send() -> Vec<CosmosMsg> {
+    vec![CosmosMsg::<Empty>::Bank(BankMsg::Send {
+        to_address: VALID_OSMO_ADDR.into(),
+        amount: vec![coin(2, DENOM)],
+    })]
+}
+
+#[test]
+#[serial]
+fn denylisted_recipient_is_blocked() {
+    let app = OsmosisTestApp::new();
+    let suite = HubChainSuite::init(&app);
+    let denylist = Contract::deploy(
+        &app,
+        &COMPLIANCE_DENYLIST_CODE_PATH,
+        &DenylistInstantiateMsg {
+            operator: suite.accounts[IOPERATOR].address(),
+        },
+        &suite.accounts[IOPERATOR],
+    )
+    .unwrap();
+
+    let vid = "test-user";
+    let (wallet_addr, _) = create_webauthn_wallet(
+        &app,
+        &suite.factory,
+        vid,
+        INIT_BALANCE,
+        &suite.accounts[IRELAYER],
+    );
+
+    let plugin_addr = register_and_install_plugin(
+        &suite,
+        vid,
+        wallet_addr.as_str(),
+        &COMPLIANCE_CODE_PATH,
+        &COMPLIANCE_HASH,
+        PluginPermission::PreTxCheck,
+        to_binary(&InstantiateMsg {
+            denylist: denylist.contract_addr.clone(),
+            allowlist: vec![],
+            allowlist_enabled: false,
+        })
+        .unwrap(),
+    );
+    let plugin = Contract::from_addr(&app, plugin_addr);
+
+    sign_and_submit(
+        &app,
+        send(),
+        vid,
+        wallet_addr.as_str(),
+        &suite.accounts[IRELAYER],
+    )
+    .unwrap();
+
+    denylist
+        .execute(
+            &DenylistExecMsg::UpdateDenylist {
+                add: vec![DenylistEntry {
+                    address: VALID_OSMO_ADDR.into(),
+                    reason: "OFAC".into(),
+                }],
+                remove: vec![],
+            },
+            &[],
+            &suite.accounts[IOPERATOR],
+        )
+        .unwrap();
+
+    sign_and_submit(
+        &app,
+        send(),
+        vid,
+        wallet_addr.as_str(),
+        &suite.accounts[IRELAYER],
+    )
+    .unwrap_err();
+
+    let status: AddressStatusResponse = plugin
+        .query(&QueryMsg::AddressStatus {
+            address: VALID_OSMO_ADDR.into(),
+        })
+        .unwrap();
+    assert_eq!(
+        status.blocked,
+        Some(BlockReason::Denylisted {
+            reason: "OFAC".into()
+        })
+    );
+}
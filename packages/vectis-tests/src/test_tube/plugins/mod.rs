@@ -0,0 +1,4 @@
+pub mod compliance;
+pub mod recurring_payments;
+pub mod spending_limit;
+pub mod tx_history;
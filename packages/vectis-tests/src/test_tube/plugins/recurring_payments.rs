@@ -0,0 +1,212 @@
+use cosmwasm_std::{coin, to_binary, CosmosMsg, Empty, WasmMsg};
+use osmosis_std::types::cosmos::bank::v1beta1::QueryBalanceRequest;
+use osmosis_test_tube::{Bank, OsmosisTestApp};
+use serial_test::serial;
+use test_tube::module::Module;
+
+use vectis_recurring_payments::{
+    contract::sv::{ExecMsg, InstantiateMsg, QueryMsg},
+    types::{ScheduleParams, SchedulesResponse, UpcomingPaymentsResponse},
+};
+use vectis_wallet::types::plugin::PluginPermission;
+
+use crate::{
+    constants::*,
+    test_tube::{
+        test_env::HubChainSuite,
+        util::{
+            contract::Contract,
+            wallet::{create_webauthn_wallet, register_and_install_plugin, sign_and_submit},
+        },
+    },
+};
+
+// Any account can trigger payments
+const IKEEPER: usize = 3;
+const PAYMENT: u128 = 2u128;
+const INTERVAL: u64 = 100;
+
+fn schedule_params() -> ScheduleParams {
+    ScheduleParams {
+        recipient: VALID_OSMO_ADDR.into(),
+        amount: vec![coin(PAYMENT, DENOM)],
+        interval: INTERVAL,
+        start: None,
+        end: None,
+    }
+}
+
+fn install_recurring_payments<'a>(
+    suite: &HubChainSuite<'a>,
+    vid: &str,
+    wallet_addr: &str,
+    schedules: Vec<ScheduleParams>,
+) -> Contract<'a> {
+    let plugin_addr = register_and_install_plugin(
+        suite,
+        vid,
+        wallet_addr,
+        &RECURRING_PAYMENTS_CODE_PATH,
+        &RECURRING_PAYMENTS_HASH,
+        PluginPermission::Exec,
+        to_binary(&InstantiateMsg { schedules }).unwrap(),
+    );
+    Contract::from_addr(suite.app, plugin_addr)
+}
+
+fn wallet_balance(app: &OsmosisTestApp, wallet_addr: &str) -> String {
+    Bank::new(app)
+        .query_balance(&QueryBalanceRequest {
+            address: wallet_addr.into(),
+            denom: DENOM.into(),
+        })
+        .unwrap()
+        .balance
+        .unwrap()
+        .amount
+}
+
+#[test]
+#[serial]
+fn keeper_triggers_payments_from_wallet() {
+    let app = OsmosisTestApp::new();
+    let suite = HubChainSuite::init(&app);
+
+    let vid = "test-user";
+    let (wallet_addr, _) = create_webauthn_wallet(
+        &app,
+        &suite.factory,
+        vid,
+        INIT_BALANCE,
+        &suite.accounts[IRELAYER],
+    );
+
+    let plugin =
+        install_recurring_payments(&suite, vid, wallet_addr.as_str(), vec![schedule_params()]);
+
+    let upcoming: UpcomingPaymentsResponse = plugin
+        .query(&QueryMsg::UpcomingPayments {
+            until: None,
+            start_after: None,
+            limit: None,
+        })
+        .unwrap();
+    assert_eq!(upcoming.payments.len(), 1);
+
+    // First payment is due on install
+    plugin
+        .execute(
+            &ExecMsg::TriggerPayments {
+                ids: None,
+                limit: None,
+            },
+            &[],
+            &suite.accounts[IKEEPER],
+        )
+        .unwrap();
+    assert_eq!(
+        wallet_balance(&app, wallet_addr.as_str()),
+        (INIT_BALANCE - PAYMENT).to_string()
+    );
+
+    // Nothing due until the interval passed
+    plugin
+        .execute(
+            &ExecMsg::TriggerPayments {
+                ids: None,
+                limit: None,
+            },
+            &[],
+            &suite.accounts[IKEEPER],
+        )
+        .unwrap_err();
+
+    app.increase_time(INTERVAL);
+
+    plugin
+        .execute(
+            &ExecMsg::TriggerPayments {
+                ids: None,
+                limit: None,
+            },
+            &[],
+            &suite.accounts[IKEEPER],
+        )
+        .unwrap();
+    assert_eq!(
+        wallet_balance(&app, wallet_addr.as_str()),
+        (INIT_BALANCE - PAYMENT * 2).to_string()
+    );
+}
+
+#[test]
+#[serial]
+fn controller_manages_schedules_through_wallet() {
+    let app = OsmosisTestApp::new();
+    let suite = HubChainSuite::init(&app);
+
+    let vid = "test-user";
+    let (wallet_addr, _) = create_webauthn_wallet(
+        &app,
+        &suite.factory,
+        vid,
+        INIT_BALANCE,
+        &suite.accounts[IRELAYER],
+    );
+
+    let plugin = install_recurring_payments(&suite, vid, wallet_addr.as_str(), vec![]);
+
+    let add_schedule_msg = ExecMsg::AddSchedule {
+        schedule: schedule_params(),
+    };
+
+    // Only the wallet can add schedules
+    plugin
+        .execute(&add_schedule_msg, &[], &suite.accounts[IKEEPER])
+        .unwrap_err();
+
+    sign_and_submit(
+        &app,
+        vec![CosmosMsg::<Empty>::Wasm(WasmMsg::Execute {
+            contract_addr: plugin.contract_addr.clone(),
+            msg: to_binary(&add_schedule_msg).unwrap(),
+            funds: vec![],
+        })],
+        vid,
+        wallet_addr.as_str(),
+        &suite.accounts[IRELAYER],
+    )
+    .unwrap();
+
+    let schedules: SchedulesResponse = plugin
+        .query(&QueryMsg::Schedules {
+            start_after: None,
+            limit: None,
+        })
+        .unwrap();
+    assert_eq!(schedules.schedules.len(), 1);
+
+    sign_and_submit(
+        &app,
+        vec![CosmosMsg::<Empty>::Wasm(WasmMsg::Execute {
+            contract_addr: plugin.contract_addr.clone(),
+            msg: to_binary(&ExecMsg::CancelSchedule {
+                id: schedules.schedules[0].id,
+            })
+            .unwrap(),
+            funds: vec![],
+        })],
+        vid,
+        wallet_addr.as_str(),
+        &suite.accounts[IRELAYER],
+    )
+    .unwrap();
+
+    let schedules: SchedulesResponse = plugin
+        .query(&QueryMsg::Schedules {
+            start_after: None,
+            limit: None,
+        })
+        .unwrap();
+    assert!(schedules.schedules.is_empty());
+}
@@ -0,0 +1,145 @@
+use cosmwasm_std::{coin, to_binary, BankMsg, CosmosMsg, Empty, Uint128, WasmMsg};
+use osmosis_test_tube::OsmosisTestApp;
+use serial_test::serial;
+
+use vectis_spending_limit_check::contract::sv::InstantiateMsg as CheckInstantiateMsg;
+use vectis_spending_limit_hook::{
+    contract::sv::{ExecMsg as HookExecMsg, InstantiateMsg as HookInstantiateMsg},
+    types::LimitParams,
+};
+use vectis_wallet::types::plugin::PluginPermission;
+
+use crate::{
+    constants::*,
+    test_tube::{
+        test_env::HubChainSuite,
+        util::wallet::{create_webauthn_wallet, register_and_install_plugin, sign_and_submit},
+    },
+};
+
+const LIMIT: u128 = 5u128;
+const PERIOD: u64 = 100;
+
+fn send(amount: u128) -> Vec<CosmosMsg> {
+    vec![CosmosMsg::<Empty>::Bank(BankMsg::Send {
+        to_address: VALID_OSMO_ADDR.into(),
+        amount: vec![coin(amount, DENOM)],
+    })]
+}
+
+// The hook records the spend and the check enforces the limit on AuthExec
+#[test]
+#[serial]
+fn spending_limit_is_enforced_on_wallet_txs() {
+    let app = OsmosisTestApp::new();
+    let suite = HubChainSuite::init(&app);
+
+    let vid = "test-user";
+    let (wallet_addr, _) = create_webauthn_wallet(
+        &app,
+        &suite.factory,
+        vid,
+        INIT_BALANCE,
+        &suite.accounts[IRELAYER],
+    );
+
+    // The hook must be installed first as the check is instantiated with its address
+    let hook_addr = register_and_install_plugin(
+        &suite,
+        vid,
+        wallet_addr.as_str(),
+        &SPENDING_LIMIT_HOOK_CODE_PATH,
+        &SPENDING_LIMIT_HOOK_HASH,
+        PluginPermission::PostTxHook,
+        to_binary(&HookInstantiateMsg {
+            limits: vec![LimitParams {
+                denom: DENOM.into(),
+                amount: Uint128::from(LIMIT),
+                period: PERIOD,
+            }],
+        })
+        .unwrap(),
+    );
+
+    register_and_install_plugin(
+        &suite,
+        vid,
+        wallet_addr.as_str(),
+        &SPENDING_LIMIT_CHECK_CODE_PATH,
+        &SPENDING_LIMIT_CHECK_HASH,
+        PluginPermission::PreTxCheck,
+        to_binary(&CheckInstantiateMsg {
+            hook: hook_addr.clone(),
+        })
+        .unwrap(),
+    );
+
+    sign_and_submit(
+        &app,
+        send(3),
+        vid,
+        wallet_addr.as_str(),
+        &suite.accounts[IRELAYER],
+    )
+    .unwrap();
+
+    // 3 + 3 is over the limit
+    sign_and_submit(
+        &app,
+        send(3),
+        vid,
+        wallet_addr.as_str(),
+        &suite.accounts[IRELAYER],
+    )
+    .unwrap_err();
+
+    sign_and_submit(
+        &app,
+        send(2),
+        vid,
+        wallet_addr.as_str(),
+        &suite.accounts[IRELAYER],
+    )
+    .unwrap();
+
+    // Spend is out of the window after the period
+    app.increase_time(PERIOD);
+    sign_and_submit(
+        &app,
+        send(3),
+        vid,
+        wallet_addr.as_str(),
+        &suite.accounts[IRELAYER],
+    )
+    .unwrap();
+
+    // Controller can raise the limit through the wallet
+    sign_and_submit(
+        &app,
+        vec![CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: hook_addr,
+            msg: to_binary(&HookExecMsg::SetLimit {
+                limit: LimitParams {
+                    denom: DENOM.into(),
+                    amount: Uint128::from(LIMIT * 2),
+                    period: PERIOD,
+                },
+            })
+            .unwrap(),
+            funds: vec![],
+        })],
+        vid,
+        wallet_addr.as_str(),
+        &suite.accounts[IRELAYER],
+    )
+    .unwrap();
+
+    sign_and_submit(
+        &app,
+        send(3),
+        vid,
+        wallet_addr.as_str(),
+        &suite.accounts[IRELAYER],
+    )
+    .unwrap();
+}
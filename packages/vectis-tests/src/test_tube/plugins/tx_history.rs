@@ -0,0 +1,89 @@
+use cosmwasm_std::{coin, to_binary, BankMsg, CosmosMsg, Empty};
+use osmosis_test_tube::OsmosisTestApp;
+use serial_test::serial;
+
+use vectis_tx_history::{
+    contract::sv::{InstantiateMsg, QueryMsg},
+    types::{MsgKind, MsgSummary, Retention, TxRecordsResponse},
+};
+use vectis_wallet::types::plugin::PluginPermission;
+
+use crate::{
+    constants::*,
+    test_tube::{
+        test_env::HubChainSuite,
+        util::{
+            contract::Contract,
+            wallet::{create_webauthn_wallet, register_and_install_plugin, sign_and_submit},
+        },
+    },
+};
+
+#[test]
+#[serial]
+fn auth_exec_is_recorded() {
+    let app = OsmosisTestApp::new();
+    let suite = HubChainSuite::init(&app);
+
+    let vid = "test-user";
+    let (wallet_addr, _) = create_webauthn_wallet(
+        &app,
+        &suite.factory,
+        vid,
+        INIT_BALANCE,
+        &suite.accounts[IRELAYER],
+    );
+
+    let plugin_addr = register_and_install_plugin(
+        &suite,
+        vid,
+        wallet_addr.as_str(),
+        &TX_HISTORY_CODE_PATH,
+        &TX_HISTORY_HASH,
+        PluginPermission::PostTxHook,
+        to_binary(&InstantiateMsg {
+            retention: Retention {
+                max_records: 10,
+                max_age: None,
+            },
+        })
+        .unwrap(),
+    );
+    let plugin = Contract::from_addr(&app, plugin_addr);
+
+    sign_and_submit(
+        &app,
+        vec![CosmosMsg::<Empty>::Bank(BankMsg::Send {
+            to_address: VALID_OSMO_ADDR.into(),
+            amount: vec![coin(2, DENOM)],
+        })],
+        vid,
+        wallet_addr.as_str(),
+        &suite.accounts[IRELAYER],
+    )
+    .unwrap();
+
+    let expected_msgs = vec![MsgSummary {
+        kind: MsgKind::BankSend,
+        counterparty: Some(VALID_OSMO_ADDR.into()),
+        funds: vec![coin(2, DENOM)],
+    }];
+
+    let latest: TxRecordsResponse = plugin
+        .query(&QueryMsg::History {
+            start_before: None,
+            limit: Some(1),
+        })
+        .unwrap();
+    assert_eq!(latest.records[0].record.msgs, expected_msgs);
+
+    let by_counterparty: TxRecordsResponse = plugin
+        .query(&QueryMsg::HistoryByCounterparty {
+            counterparty: VALID_OSMO_ADDR.into(),
+            start_before: None,
+            limit: None,
+        })
+        .unwrap();
+    assert_eq!(by_counterparty.records.len(), 1);
+    assert_eq!(by_counterparty.records[0].record.msgs, expected_msgs);
+}
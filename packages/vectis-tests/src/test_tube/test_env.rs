@@ -350,6 +350,40 @@ impl HubChainSuite<'_> {
         self.test_contracts.post_tx.2 = 2;
         self.test_contracts.exec.2 = 3;
     }
+
+    /// Stores and registers a plugin that is not one of the `test_contracts`,
+    /// returns the registry id of the plugin
+    pub fn register_plugin(&self, code_path: &str, code_hash: &'static str) -> u64 {
+        let code_id = Contract::store_code(self.app, code_path, &self.accounts[IDEPLOYER]);
+
+        vectis_committee::execute(
+            self.app,
+            self.deployer.clone(),
+            self.plugin_registry.clone(),
+            &registry_management_trait::ExecMsg::RegisterPlugin {
+                code_data: test_plugin_code_data(code_id, code_hash),
+                metadata_data: test_plugin_metadata(),
+            },
+            &[coin(REGISTRY_FEE, "uosmo")],
+            &self.accounts[ICOMMITTEE],
+        )
+        .unwrap();
+
+        let registry = Contract::from_addr(self.app, self.plugin_registry.clone());
+        let plugins: PluginsResponse = registry
+            .query(&registry_management_trait::QueryMsg::GetPlugins {
+                limit: None,
+                start_after: None,
+            })
+            .unwrap();
+
+        plugins
+            .plugins
+            .into_iter()
+            .find(|p| p.versions.values().any(|v| v.code_id == code_id))
+            .unwrap()
+            .id
+    }
 }
 
 pub fn add_item_prop_msg(deployer: String, key: VectisActors, value: String) -> cw3flexExecMsg {
@@ -2,9 +2,9 @@ use crate::{
     constants::*,
     helpers::{sign_and_create_relay_tx, webauthn_entity},
     passkey::*,
-    test_tube::util::contract::Contract,
+    test_tube::{test_env::HubChainSuite, util::contract::Contract},
 };
-use cosmwasm_std::{coin, to_binary, Addr, CosmosMsg};
+use cosmwasm_std::{coin, to_binary, Addr, Binary, CosmosMsg};
 use osmosis_std::types::cosmwasm::wasm::v1::MsgExecuteContractResponse;
 use osmosis_test_tube::OsmosisTestApp;
 use test_tube::{RunnerExecuteResult, SigningAccount};
@@ -139,9 +139,6 @@ pub fn add_test_plugin(
     relayer: &SigningAccount,
     plugin_id: u64,
 ) -> RunnerExecuteResult<MsgExecuteContractResponse> {
-    let wallet = Contract::from_addr(app, wallet_addr.to_string());
-    let info: WalletInfo = wallet.query(&WalletQueryMsg::Info {}).unwrap();
-
     let permission = match plugin_id {
         1 => PluginPermission::PreTxCheck,
         2 => PluginPermission::PostTxHook,
@@ -150,13 +147,36 @@ pub fn add_test_plugin(
         _ => PluginPermission::PreTxCheck,
     };
 
+    install_plugin(
+        app,
+        vid,
+        wallet_addr,
+        relayer,
+        plugin_id,
+        permission,
+        to_binary(&EmptyInstantiateMsg {}).unwrap(),
+    )
+}
+
+pub fn install_plugin(
+    app: &OsmosisTestApp,
+    vid: &str,
+    wallet_addr: &str,
+    relayer: &SigningAccount,
+    plugin_id: u64,
+    permission: PluginPermission,
+    instantiate_msg: Binary,
+) -> RunnerExecuteResult<MsgExecuteContractResponse> {
+    let wallet = Contract::from_addr(app, wallet_addr.to_string());
+    let info: WalletInfo = wallet.query(&WalletQueryMsg::Info {}).unwrap();
+
     let install_plugin_msg = WalletPluginExecMsg::InstallPlugins {
         install: vec![PluginInstallParams {
             src: PluginSource::VectisRegistry(plugin_id, None),
             permission,
             label: "plugin_install".into(),
             funds: vec![],
-            instantiate_msg: to_binary(&EmptyInstantiateMsg {}).unwrap(),
+            instantiate_msg,
         }],
     };
     let relay_tx = sign_and_create_relay_tx(
@@ -177,3 +197,37 @@ pub fn add_test_plugin(
         relayer,
     )
 }
+
+/// Registers the plugin at `code_path` and installs it on the wallet,
+/// returns the address of the installed plugin
+pub fn register_and_install_plugin(
+    suite: &HubChainSuite,
+    vid: &str,
+    wallet_addr: &str,
+    code_path: &str,
+    code_hash: &'static str,
+    permission: PluginPermission,
+    instantiate_msg: Binary,
+) -> String {
+    let plugin_id = suite.register_plugin(code_path, code_hash);
+    let res = install_plugin(
+        suite.app,
+        vid,
+        wallet_addr,
+        &suite.accounts[IRELAYER],
+        plugin_id,
+        permission,
+        instantiate_msg,
+    )
+    .unwrap();
+
+    res.events
+        .into_iter()
+        .find(|x| x.ty == "instantiate")
+        .unwrap()
+        .attributes
+        .into_iter()
+        .find(|x| x.key == "_contract_address")
+        .unwrap()
+        .value
+}
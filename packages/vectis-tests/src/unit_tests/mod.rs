@@ -1,6 +1,7 @@
 #![cfg(test)]
 pub mod factory_mgmt;
 pub mod factory_services;
+pub mod plugins;
 pub mod registry_mgmt;
 pub mod subscriptions;
 pub mod utils;
@@ -0,0 +1,338 @@
+use cosmwasm_std::{BankMsg, IbcMsg, IbcTimeout, WasmMsg};
+use vectis_compliance::{
+    contract::sv::multitest_utils::ComplianceProxy,
+    error::ComplianceError,
+    types::{BlockReason, BlockedMsg},
+};
+use vectis_compliance_denylist::{
+    contract::sv::multitest_utils::ComplianceDenylistProxy, error::ComplianceDenylistError,
+    types::DenylistEntry,
+};
+
+use crate::unit_tests::utils::*;
+
+const PROXY: &str = "proxy";
+const OPERATOR: &str = "operator";
+const SANCTIONED: &str = "sanctioned";
+const FRIEND: &str = "friend";
+const STRANGER: &str = "stranger";
+
+fn send(to: &str) -> CosmosMsg {
+    CosmosMsg::Bank(BankMsg::Send {
+        to_address: to.into(),
+        amount: vec![coin(1, DENOM)],
+    })
+}
+
+fn execute(contract: &str) -> CosmosMsg {
+    CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: contract.into(),
+        msg: to_binary(&Empty {}).unwrap(),
+        funds: vec![coin(1, DENOM)],
+    })
+}
+
+fn instantiate(funds: Vec<Coin>) -> CosmosMsg {
+    CosmosMsg::Wasm(WasmMsg::Instantiate {
+        admin: None,
+        code_id: 1,
+        msg: to_binary(&Empty {}).unwrap(),
+        funds,
+        label: "contract".into(),
+    })
+}
+
+fn denylist(denylist: &ComplianceDenylistProxy<'_, MtApp>, address: &str) {
+    denylist
+        .update_denylist(
+            vec![DenylistEntry {
+                address: address.into(),
+                reason: "OFAC".into(),
+            }],
+            vec![],
+        )
+        .call(OPERATOR)
+        .unwrap();
+}
+
+fn setup<'a>(
+    app: &'a App<MtApp>,
+    allowlist: Vec<String>,
+    allowlist_enabled: bool,
+) -> (
+    ComplianceDenylistProxy<'a, MtApp>,
+    ComplianceProxy<'a, MtApp>,
+) {
+    let denylist = ComplianceDenylistCodeId::store_code(app)
+        .instantiate(OPERATOR.into())
+        .with_label("Compliance Denylist")
+        .call(OPERATOR)
+        .unwrap();
+    let plugin = ComplianceCodeId::store_code(app)
+        .instantiate(
+            denylist.contract_addr.to_string(),
+            allowlist,
+            allowlist_enabled,
+        )
+        .with_label("Compliance")
+        .call(PROXY)
+        .unwrap();
+    (denylist, plugin)
+}
+
+#[test]
+fn denylisted_recipients_are_blocked() {
+    let app = App::default();
+    let (shared, plugin) = setup(&app, vec![], false);
+
+    denylist(&shared, SANCTIONED);
+
+    assert!(pre_tx_check(
+        &app,
+        &plugin.contract_addr,
+        vec![send(STRANGER)]
+    ));
+    assert!(!pre_tx_check(
+        &app,
+        &plugin.contract_addr,
+        vec![send(SANCTIONED)]
+    ));
+    assert!(!pre_tx_check(
+        &app,
+        &plugin.contract_addr,
+        vec![send(STRANGER), execute(SANCTIONED)]
+    ));
+    assert!(!pre_tx_check(
+        &app,
+        &plugin.contract_addr,
+        vec![CosmosMsg::Ibc(IbcMsg::Transfer {
+            channel_id: "channel-0".into(),
+            to_address: SANCTIONED.into(),
+            amount: coin(1, DENOM),
+            timeout: IbcTimeout::with_timestamp(app.app().block_info().time.plus_seconds(100)),
+        })]
+    ));
+
+    // Bech32 is case insensitive
+    assert!(!pre_tx_check(
+        &app,
+        &plugin.contract_addr,
+        vec![send(&SANCTIONED.to_uppercase())]
+    ));
+
+    let status = plugin.address_status(SANCTIONED.into()).unwrap();
+    assert_eq!(
+        status.blocked,
+        Some(BlockReason::Denylisted {
+            reason: "OFAC".into()
+        })
+    );
+    assert_eq!(
+        plugin.address_status(STRANGER.into()).unwrap().blocked,
+        None
+    );
+
+    // Each blocked address is only reported once, lowercased
+    let blocked = plugin
+        .check_msgs(vec![
+            send(&SANCTIONED.to_uppercase()),
+            execute(SANCTIONED),
+            send(STRANGER),
+        ])
+        .unwrap()
+        .blocked;
+    assert_eq!(
+        blocked,
+        vec![BlockedMsg {
+            address: Some(SANCTIONED.into()),
+            reason: BlockReason::Denylisted {
+                reason: "OFAC".into()
+            },
+        }]
+    );
+
+    shared
+        .update_denylist(vec![], vec![SANCTIONED.to_uppercase()])
+        .call(OPERATOR)
+        .unwrap();
+    assert!(pre_tx_check(
+        &app,
+        &plugin.contract_addr,
+        vec![send(SANCTIONED)]
+    ));
+    assert!(shared.denylist(None, None).unwrap().entries.is_empty());
+}
+
+#[test]
+fn denylist_is_shared_by_all_plugins() {
+    let app = App::default();
+    let (shared, plugin) = setup(&app, vec![], false);
+    let other_plugin = ComplianceCodeId::store_code(&app)
+        .instantiate(shared.contract_addr.to_string(), vec![], false)
+        .with_label("Compliance")
+        .call("other_proxy")
+        .unwrap();
+
+    denylist(&shared, &SANCTIONED.to_uppercase());
+    assert_eq!(
+        shared.reason(SANCTIONED.into()).unwrap(),
+        Some("OFAC".into())
+    );
+    assert!(!pre_tx_check(
+        &app,
+        &plugin.contract_addr,
+        vec![send(SANCTIONED)]
+    ));
+    assert!(!pre_tx_check(
+        &app,
+        &other_plugin.contract_addr,
+        vec![send(SANCTIONED)]
+    ));
+}
+
+#[test]
+fn msgs_moving_unchecked_funds_are_blocked() {
+    let app = App::default();
+    let (_, plugin) = setup(&app, vec![], false);
+
+    assert!(pre_tx_check(
+        &app,
+        &plugin.contract_addr,
+        vec![instantiate(vec![])]
+    ));
+    assert!(!pre_tx_check(
+        &app,
+        &plugin.contract_addr,
+        vec![instantiate(vec![coin(1, DENOM)])]
+    ));
+
+    let stargate = CosmosMsg::Stargate {
+        type_url: "/cosmos.bank.v1beta1.MsgSend".into(),
+        value: Binary::default(),
+    };
+    assert!(!pre_tx_check(
+        &app,
+        &plugin.contract_addr,
+        vec![stargate.clone()]
+    ));
+
+    let blocked = plugin
+        .check_msgs(vec![send(STRANGER), stargate])
+        .unwrap()
+        .blocked;
+    assert_eq!(
+        blocked,
+        vec![BlockedMsg {
+            address: None,
+            reason: BlockReason::UncheckedFunds {
+                msg: "/cosmos.bank.v1beta1.MsgSend".into()
+            },
+        }]
+    );
+}
+
+#[test]
+fn allowlist_restricts_recipients_when_enabled() {
+    let app = App::default();
+    let (shared, plugin) = setup(&app, vec![FRIEND.to_uppercase()], true);
+
+    assert!(pre_tx_check(
+        &app,
+        &plugin.contract_addr,
+        vec![send(FRIEND)]
+    ));
+    assert!(!pre_tx_check(
+        &app,
+        &plugin.contract_addr,
+        vec![send(STRANGER)]
+    ));
+    assert_eq!(
+        plugin.address_status(STRANGER.into()).unwrap().blocked,
+        Some(BlockReason::NotAllowlisted)
+    );
+
+    // The proxy and the plugin itself can always be called
+    assert!(pre_tx_check(
+        &app,
+        &plugin.contract_addr,
+        vec![execute(PROXY), execute(plugin.contract_addr.as_str())]
+    ));
+
+    // The denylist takes precedence over the allowlist
+    denylist(&shared, FRIEND);
+    assert!(!pre_tx_check(
+        &app,
+        &plugin.contract_addr,
+        vec![send(FRIEND)]
+    ));
+
+    plugin
+        .update_allowlist(vec![STRANGER.into()], vec![FRIEND.into()])
+        .call(PROXY)
+        .unwrap();
+    assert!(pre_tx_check(
+        &app,
+        &plugin.contract_addr,
+        vec![send(STRANGER)]
+    ));
+    assert_eq!(
+        plugin.allowlist(None, None).unwrap().addresses,
+        vec![STRANGER.to_string()]
+    );
+
+    plugin.set_allowlist_enabled(false).call(PROXY).unwrap();
+    assert!(!plugin.config().unwrap().allowlist_enabled);
+    assert!(pre_tx_check(
+        &app,
+        &plugin.contract_addr,
+        vec![send("anyone")]
+    ));
+}
+
+#[test]
+fn lists_are_managed_by_their_maintainers() {
+    let app = App::default();
+    let (shared, plugin) = setup(&app, vec![], false);
+
+    let config = plugin.config().unwrap();
+    assert_eq!(config.owner, Addr::unchecked(PROXY));
+    assert_eq!(config.denylist, shared.contract_addr);
+    assert_eq!(shared.operator().unwrap(), Addr::unchecked(OPERATOR));
+
+    // The user cannot remove itself from the denylist
+    let err = shared
+        .update_denylist(vec![], vec![SANCTIONED.into()])
+        .call(PROXY)
+        .unwrap_err();
+    assert_eq!(err, ComplianceDenylistError::Unauthorized);
+
+    // The operator cannot change the allowlist
+    let err = plugin
+        .update_allowlist(vec![SANCTIONED.into()], vec![])
+        .call(OPERATOR)
+        .unwrap_err();
+    assert_eq!(err, ComplianceError::Unauthorized);
+    let err = plugin
+        .set_allowlist_enabled(true)
+        .call(OPERATOR)
+        .unwrap_err();
+    assert_eq!(err, ComplianceError::Unauthorized);
+
+    let err = shared
+        .update_operator(PROXY.into())
+        .call(PROXY)
+        .unwrap_err();
+    assert_eq!(err, ComplianceDenylistError::Unauthorized);
+
+    shared
+        .update_operator("new_operator".into())
+        .call(OPERATOR)
+        .unwrap();
+    assert_eq!(shared.operator().unwrap(), Addr::unchecked("new_operator"));
+
+    let err = shared
+        .update_denylist(vec![], vec![])
+        .call(OPERATOR)
+        .unwrap_err();
+    assert_eq!(err, ComplianceDenylistError::Unauthorized);
+}
@@ -0,0 +1,4 @@
+mod compliance;
+mod recurring_payments;
+mod spending_limit;
+mod tx_history;
@@ -0,0 +1,355 @@
+use vectis_recurring_payments::{error::RecurringPaymentsError, types::ScheduleParams};
+
+use crate::unit_tests::utils::*;
+
+const RECIPIENT: &str = "recipient";
+const KEEPER: &str = "keeper";
+const PAYMENT: u128 = 2u128;
+const INTERVAL: u64 = 100;
+
+fn mock_proxy(app: &App<MtApp>) -> Addr {
+    let code_id = app.app_mut().store_code(contract_mock_proxy());
+    let proxy = app
+        .app_mut()
+        .instantiate_contract(
+            code_id,
+            Addr::unchecked("deployer"),
+            &Empty {},
+            &[],
+            "Mock Proxy",
+            None,
+        )
+        .unwrap();
+
+    app.app_mut()
+        .init_modules(|router, _, storage| {
+            router
+                .bank
+                .init_balance(storage, &proxy, vec![coin(INIT_BALANCE, DENOM)])
+        })
+        .unwrap();
+
+    proxy
+}
+
+fn schedule_params(start: Option<Timestamp>, end: Option<Timestamp>) -> ScheduleParams {
+    ScheduleParams {
+        recipient: RECIPIENT.into(),
+        amount: vec![coin(PAYMENT, DENOM)],
+        interval: INTERVAL,
+        start,
+        end,
+    }
+}
+
+fn recipient_balance(app: &App<MtApp>) -> u128 {
+    app.app()
+        .wrap()
+        .query_balance(RECIPIENT, DENOM)
+        .unwrap()
+        .amount
+        .u128()
+}
+
+#[test]
+fn keeper_triggers_due_payments() {
+    let app = App::default();
+    let proxy = mock_proxy(&app);
+    let start = app.app().block_info().time;
+
+    let plugin = RecurringPaymentsCodeId::store_code(&app)
+        .instantiate(vec![schedule_params(None, None)])
+        .with_label("Recurring Payments")
+        .call(proxy.as_str())
+        .unwrap();
+
+    assert_eq!(plugin.owner().unwrap(), proxy);
+
+    // First payment is due straight away
+    plugin.trigger_payments(None, None).call(KEEPER).unwrap();
+    assert_eq!(recipient_balance(&app), PAYMENT);
+
+    // Nothing is due until the interval has passed
+    let err = plugin
+        .trigger_payments(None, None)
+        .call(KEEPER)
+        .unwrap_err();
+    assert_eq!(err, RecurringPaymentsError::NoPaymentDue);
+
+    pass_time(&app, INTERVAL);
+    plugin
+        .trigger_payments(Some(vec![1]), None)
+        .call(KEEPER)
+        .unwrap();
+    assert_eq!(recipient_balance(&app), PAYMENT * 2);
+
+    let schedule = plugin.schedule(1).unwrap().schedule;
+    assert_eq!(schedule.payments_made, 2);
+    assert_eq!(schedule.next_payment, start.plus_seconds(INTERVAL * 2));
+
+    // Repeated ids are only paid once and unknown ids are skipped
+    pass_time(&app, INTERVAL * 2);
+    plugin
+        .trigger_payments(Some(vec![1, 1, 1, 1, 1, 99]), None)
+        .call(KEEPER)
+        .unwrap();
+    assert_eq!(recipient_balance(&app), PAYMENT * 3);
+}
+
+#[test]
+fn failed_payment_stays_due_without_blocking_other_schedules() {
+    let app = App::default();
+    let proxy = mock_proxy(&app);
+    let start = app.app().block_info().time;
+
+    // The proxy holds no DENOM1
+    let mut unpayable = schedule_params(None, None);
+    unpayable.amount = vec![coin(PAYMENT, DENOM1)];
+    let mut last_unpayable = unpayable.clone();
+    last_unpayable.end = Some(start.plus_seconds(1));
+    let plugin = RecurringPaymentsCodeId::store_code(&app)
+        .instantiate(vec![unpayable, schedule_params(None, None), last_unpayable])
+        .with_label("Recurring Payments")
+        .call(proxy.as_str())
+        .unwrap();
+
+    plugin.trigger_payments(None, None).call(KEEPER).unwrap();
+    assert_eq!(recipient_balance(&app), PAYMENT);
+    assert_eq!(plugin.schedule(2).unwrap().schedule.payments_made, 1);
+
+    // Failed payments are still due, including the last payment of a schedule
+    for id in [1, 3] {
+        let failed = plugin.schedule(id).unwrap().schedule;
+        assert_eq!(failed.payments_made, 0);
+        assert_eq!(failed.payments_failed, 1);
+        assert_eq!(failed.next_payment, start);
+    }
+    let upcoming = plugin
+        .upcoming_payments(Some(start), None, None)
+        .unwrap()
+        .payments;
+    assert_eq!(upcoming.len(), 2);
+
+    app.app_mut()
+        .init_modules(|router, _, storage| {
+            router
+                .bank
+                .init_balance(storage, &proxy, vec![coin(PAYMENT * 2, DENOM1)])
+        })
+        .unwrap();
+    plugin
+        .trigger_payments(Some(vec![1, 3]), None)
+        .call(KEEPER)
+        .unwrap();
+    let balance = app.app().wrap().query_balance(RECIPIENT, DENOM1).unwrap();
+    assert_eq!(balance.amount.u128(), PAYMENT * 2);
+    assert_eq!(plugin.schedule(1).unwrap().schedule.payments_made, 1);
+    plugin.schedule(3).unwrap_err();
+}
+
+#[test]
+fn missed_payments_are_caught_up_one_at_a_time() {
+    let app = App::default();
+    let proxy = mock_proxy(&app);
+
+    let plugin = RecurringPaymentsCodeId::store_code(&app)
+        .instantiate(vec![schedule_params(None, None)])
+        .with_label("Recurring Payments")
+        .call(proxy.as_str())
+        .unwrap();
+
+    // Keeper was away for 2 intervals, so 3 payments are due
+    pass_time(&app, INTERVAL * 2);
+    for _ in 0..3 {
+        plugin.trigger_payments(None, None).call(KEEPER).unwrap();
+    }
+    assert_eq!(recipient_balance(&app), PAYMENT * 3);
+
+    let err = plugin
+        .trigger_payments(None, None)
+        .call(KEEPER)
+        .unwrap_err();
+    assert_eq!(err, RecurringPaymentsError::NoPaymentDue);
+}
+
+#[test]
+fn schedule_is_removed_after_end() {
+    let app = App::default();
+    let proxy = mock_proxy(&app);
+    let start = app.app().block_info().time;
+
+    let plugin = RecurringPaymentsCodeId::store_code(&app)
+        .instantiate(vec![schedule_params(
+            None,
+            Some(start.plus_seconds(INTERVAL + 1)),
+        )])
+        .with_label("Recurring Payments")
+        .call(proxy.as_str())
+        .unwrap();
+
+    plugin.trigger_payments(None, None).call(KEEPER).unwrap();
+    pass_time(&app, INTERVAL);
+    plugin.trigger_payments(None, None).call(KEEPER).unwrap();
+    assert_eq!(recipient_balance(&app), PAYMENT * 2);
+
+    // The next payment would be after the end
+    plugin.schedule(1).unwrap_err();
+    assert!(plugin.schedules(None, None).unwrap().schedules.is_empty());
+
+    pass_time(&app, INTERVAL);
+    let err = plugin
+        .trigger_payments(None, None)
+        .call(KEEPER)
+        .unwrap_err();
+    assert_eq!(err, RecurringPaymentsError::NoPaymentDue);
+}
+
+#[test]
+fn upcoming_payments_are_ordered_by_due_time() {
+    let app = App::default();
+    let proxy = mock_proxy(&app);
+    let now = app.app().block_info().time;
+
+    let plugin = RecurringPaymentsCodeId::store_code(&app)
+        .instantiate(vec![
+            schedule_params(Some(now.plus_seconds(INTERVAL * 2)), None),
+            schedule_params(Some(now.plus_seconds(INTERVAL)), None),
+        ])
+        .with_label("Recurring Payments")
+        .call(proxy.as_str())
+        .unwrap();
+
+    let upcoming = plugin.upcoming_payments(None, None, None).unwrap().payments;
+    assert_eq!(upcoming.len(), 2);
+    assert_eq!(upcoming[0].schedule_id, 2);
+    assert_eq!(upcoming[0].due, now.plus_seconds(INTERVAL));
+    assert_eq!(upcoming[1].schedule_id, 1);
+    assert_eq!(upcoming[1].due, now.plus_seconds(INTERVAL * 2));
+
+    let upcoming = plugin
+        .upcoming_payments(Some(now.plus_seconds(INTERVAL)), None, None)
+        .unwrap()
+        .payments;
+    assert_eq!(upcoming.len(), 1);
+    assert_eq!(upcoming[0].schedule_id, 2);
+
+    let upcoming = plugin
+        .upcoming_payments(None, Some((upcoming[0].due, 2)), None)
+        .unwrap()
+        .payments;
+    assert_eq!(upcoming.len(), 1);
+    assert_eq!(upcoming[0].schedule_id, 1);
+
+    // Not yet started
+    let err = plugin
+        .trigger_payments(None, None)
+        .call(KEEPER)
+        .unwrap_err();
+    assert_eq!(err, RecurringPaymentsError::NoPaymentDue);
+}
+
+#[test]
+fn only_owner_can_manage_schedules() {
+    let app = App::default();
+    let proxy = mock_proxy(&app);
+
+    let plugin = RecurringPaymentsCodeId::store_code(&app)
+        .instantiate(vec![])
+        .with_label("Recurring Payments")
+        .call(proxy.as_str())
+        .unwrap();
+
+    let err = plugin
+        .add_schedule(schedule_params(None, None))
+        .call(KEEPER)
+        .unwrap_err();
+    assert_eq!(err, RecurringPaymentsError::Unauthorized);
+
+    plugin
+        .add_schedule(schedule_params(None, None))
+        .call(proxy.as_str())
+        .unwrap();
+    assert_eq!(plugin.schedules(None, None).unwrap().schedules.len(), 1);
+
+    let err = plugin.cancel_schedule(1).call(KEEPER).unwrap_err();
+    assert_eq!(err, RecurringPaymentsError::Unauthorized);
+
+    plugin.cancel_schedule(1).call(proxy.as_str()).unwrap();
+    plugin.schedule(1).unwrap_err();
+    assert!(plugin
+        .upcoming_payments(None, None, None)
+        .unwrap()
+        .payments
+        .is_empty());
+
+    let err = plugin.cancel_schedule(1).call(proxy.as_str()).unwrap_err();
+    assert_eq!(err, RecurringPaymentsError::ScheduleNotFound(1));
+}
+
+#[test]
+fn invalid_schedules_are_rejected() {
+    let app = App::default();
+    let proxy = mock_proxy(&app);
+    let now = app.app().block_info().time;
+
+    let plugin = RecurringPaymentsCodeId::store_code(&app)
+        .instantiate(vec![])
+        .with_label("Recurring Payments")
+        .call(proxy.as_str())
+        .unwrap();
+
+    let mut params = schedule_params(None, None);
+    params.interval = 0;
+    let err = plugin
+        .add_schedule(params)
+        .call(proxy.as_str())
+        .unwrap_err();
+    assert_eq!(
+        err,
+        RecurringPaymentsError::InvalidSchedule("interval must be greater than 0".into())
+    );
+
+    let mut params = schedule_params(None, None);
+    params.interval = u64::MAX;
+    let err = plugin
+        .add_schedule(params)
+        .call(proxy.as_str())
+        .unwrap_err();
+    assert_eq!(
+        err,
+        RecurringPaymentsError::InvalidSchedule("interval is too large".into())
+    );
+
+    // i.e. a start given in seconds instead of nanos
+    let err = plugin
+        .add_schedule(schedule_params(
+            Some(Timestamp::from_nanos(1_700_000_000)),
+            None,
+        ))
+        .call(proxy.as_str())
+        .unwrap_err();
+    assert_eq!(
+        err,
+        RecurringPaymentsError::InvalidSchedule("start must not be in the past".into())
+    );
+
+    let mut params = schedule_params(None, None);
+    params.amount = vec![coin(0, DENOM)];
+    let err = plugin
+        .add_schedule(params)
+        .call(proxy.as_str())
+        .unwrap_err();
+    assert_eq!(
+        err,
+        RecurringPaymentsError::InvalidSchedule("amount must not be empty or zero".into())
+    );
+
+    let err = plugin
+        .add_schedule(schedule_params(Some(now), Some(now)))
+        .call(proxy.as_str())
+        .unwrap_err();
+    assert_eq!(
+        err,
+        RecurringPaymentsError::InvalidSchedule("end must be after start".into())
+    );
+}
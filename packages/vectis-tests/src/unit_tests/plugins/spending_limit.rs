@@ -0,0 +1,229 @@
+use cosmwasm_std::{BankMsg, IbcMsg, IbcTimeout, WasmMsg};
+use vectis_spending_limit_check::error::SpendingLimitCheckError;
+use vectis_spending_limit_hook::{error::SpendingLimitError, types::LimitParams};
+
+use crate::unit_tests::utils::*;
+
+const PROXY: &str = "proxy";
+const RECIPIENT: &str = "recipient";
+const LIMIT: u128 = 10u128;
+const PERIOD: u64 = 100;
+
+fn limit_params(denom: &str) -> LimitParams {
+    LimitParams {
+        denom: denom.into(),
+        amount: Uint128::from(LIMIT),
+        period: PERIOD,
+    }
+}
+
+fn send(amount: u128, denom: &str) -> CosmosMsg {
+    CosmosMsg::Bank(BankMsg::Send {
+        to_address: RECIPIENT.into(),
+        amount: vec![coin(amount, denom)],
+    })
+}
+
+#[test]
+fn spend_is_checked_against_rolling_window() {
+    let app = App::default();
+    let hook = SpendingLimitHookCodeId::store_code(&app)
+        .instantiate(vec![limit_params(DENOM)])
+        .with_label("Spending Limit Hook")
+        .call(PROXY)
+        .unwrap();
+
+    assert!(hook.check_msgs(vec![send(LIMIT, DENOM)]).unwrap());
+    assert!(!hook.check_msgs(vec![send(LIMIT + 1, DENOM)]).unwrap());
+
+    post_tx_hook(&app, &hook.contract_addr, PROXY, vec![send(6, DENOM)]).unwrap();
+    let limit = hook.limit(DENOM.into()).unwrap();
+    assert_eq!(limit.spent, Uint128::from(6u128));
+    assert_eq!(limit.remaining, Uint128::from(4u128));
+
+    assert!(hook.check_msgs(vec![send(4, DENOM)]).unwrap());
+    assert!(!hook.check_msgs(vec![send(5, DENOM)]).unwrap());
+    // Msgs in the same tx are added up
+    assert!(!hook
+        .check_msgs(vec![send(2, DENOM), send(3, DENOM)])
+        .unwrap());
+
+    // Spend is still in the window just before the period passed
+    pass_time(&app, PERIOD / 2);
+    post_tx_hook(&app, &hook.contract_addr, PROXY, vec![send(4, DENOM)]).unwrap();
+    pass_time(&app, PERIOD / 2 - 1);
+    assert_eq!(hook.limit(DENOM.into()).unwrap().remaining, Uint128::zero());
+    assert!(!hook.check_msgs(vec![send(1, DENOM)]).unwrap());
+
+    // Only the first spend dropped out of the window
+    pass_time(&app, 1);
+    let limit = hook.limit(DENOM.into()).unwrap();
+    assert_eq!(limit.spent, Uint128::from(4u128));
+    assert!(hook.check_msgs(vec![send(6, DENOM)]).unwrap());
+}
+
+#[test]
+fn spend_includes_contract_funds_and_ibc_transfers() {
+    let app = App::default();
+    let hook = SpendingLimitHookCodeId::store_code(&app)
+        .instantiate(vec![limit_params(DENOM), limit_params(DENOM1)])
+        .with_label("Spending Limit Hook")
+        .call(PROXY)
+        .unwrap();
+
+    let msgs = vec![
+        send(3, DENOM),
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "contract".into(),
+            msg: to_binary(&Empty {}).unwrap(),
+            funds: vec![coin(3, DENOM), coin(LIMIT, DENOM1)],
+        }),
+        CosmosMsg::Ibc(IbcMsg::Transfer {
+            channel_id: "channel-0".into(),
+            to_address: RECIPIENT.into(),
+            amount: coin(3, DENOM),
+            timeout: IbcTimeout::with_timestamp(app.app().block_info().time.plus_seconds(PERIOD)),
+        }),
+        // Denoms without limits are not restricted nor recorded
+        send(LIMIT * 2, DENOM2),
+    ];
+    assert!(hook.check_msgs(msgs.clone()).unwrap());
+
+    post_tx_hook(&app, &hook.contract_addr, PROXY, msgs).unwrap();
+    let limits = hook.limits(None, None).unwrap().limits;
+    assert_eq!(limits.len(), 2);
+    assert_eq!(limits[0].denom, DENOM1);
+    assert_eq!(limits[0].spent, Uint128::from(LIMIT));
+    assert_eq!(limits[1].denom, DENOM);
+    assert_eq!(limits[1].spent, Uint128::from(9u128));
+
+    assert!(!hook.check_msgs(vec![send(1, DENOM1)]).unwrap());
+    assert!(hook.check_msgs(vec![send(1, DENOM)]).unwrap());
+    assert!(hook.check_msgs(vec![send(LIMIT, DENOM2)]).unwrap());
+}
+
+#[test]
+fn stargate_msgs_are_rejected_while_limited() {
+    let app = App::default();
+    let hook = SpendingLimitHookCodeId::store_code(&app)
+        .instantiate(vec![])
+        .with_label("Spending Limit Hook")
+        .call(PROXY)
+        .unwrap();
+
+    let stargate = CosmosMsg::Stargate {
+        type_url: "/cosmos.bank.v1beta1.MsgSend".into(),
+        value: Binary::default(),
+    };
+    assert!(hook.check_msgs(vec![stargate.clone()]).unwrap());
+
+    hook.set_limit(limit_params(DENOM)).call(PROXY).unwrap();
+    assert!(!hook.check_msgs(vec![stargate.clone()]).unwrap());
+    assert!(!hook.check_msgs(vec![send(1, DENOM), stargate]).unwrap());
+}
+
+#[test]
+fn only_owner_can_set_limits_and_call_hook() {
+    let app = App::default();
+    let hook = SpendingLimitHookCodeId::store_code(&app)
+        .instantiate(vec![])
+        .with_label("Spending Limit Hook")
+        .call(PROXY)
+        .unwrap();
+
+    assert_eq!(hook.owner().unwrap(), Addr::unchecked(PROXY));
+
+    let err = hook
+        .set_limit(limit_params(DENOM))
+        .call(RECIPIENT)
+        .unwrap_err();
+    assert_eq!(err, SpendingLimitError::Unauthorized);
+
+    let mut params = limit_params(DENOM);
+    params.period = 0;
+    let err = hook.set_limit(params).call(PROXY).unwrap_err();
+    assert_eq!(
+        err,
+        SpendingLimitError::InvalidLimit("period must be greater than 0".into())
+    );
+
+    hook.set_limit(limit_params(DENOM)).call(PROXY).unwrap();
+
+    // Others cannot record spend to lock the proxy out of its limit
+    let err = post_tx_hook(
+        &app,
+        &hook.contract_addr,
+        RECIPIENT,
+        vec![send(LIMIT, DENOM)],
+    )
+    .unwrap_err();
+    assert_eq!(
+        err.downcast::<SpendingLimitError>().unwrap(),
+        SpendingLimitError::Unauthorized
+    );
+
+    post_tx_hook(&app, &hook.contract_addr, PROXY, vec![send(LIMIT, DENOM)]).unwrap();
+    assert!(!hook.check_msgs(vec![send(1, DENOM)]).unwrap());
+
+    let err = hook.remove_limit(DENOM.into()).call(RECIPIENT).unwrap_err();
+    assert_eq!(err, SpendingLimitError::Unauthorized);
+
+    hook.remove_limit(DENOM.into()).call(PROXY).unwrap();
+    hook.limit(DENOM.into()).unwrap_err();
+    assert!(hook.check_msgs(vec![send(LIMIT, DENOM)]).unwrap());
+
+    // Recorded spend is removed with the limit
+    hook.set_limit(limit_params(DENOM)).call(PROXY).unwrap();
+    assert_eq!(
+        hook.limit(DENOM.into()).unwrap().remaining,
+        Uint128::from(LIMIT)
+    );
+
+    let err = hook.remove_limit(DENOM1.into()).call(PROXY).unwrap_err();
+    assert_eq!(err, SpendingLimitError::LimitNotFound(DENOM1.into()));
+}
+
+#[test]
+fn pre_tx_check_is_forwarded_to_hook() {
+    let app = App::default();
+    let hook = SpendingLimitHookCodeId::store_code(&app)
+        .instantiate(vec![limit_params(DENOM)])
+        .with_label("Spending Limit Hook")
+        .call(PROXY)
+        .unwrap();
+    let check_code_id = SpendingLimitCheckCodeId::store_code(&app);
+
+    // The hook must belong to the same proxy
+    let err = check_code_id
+        .instantiate(hook.contract_addr.to_string())
+        .with_label("Spending Limit Check")
+        .call(RECIPIENT)
+        .unwrap_err();
+    assert_eq!(err, SpendingLimitCheckError::Unauthorized);
+
+    let check = check_code_id
+        .instantiate(hook.contract_addr.to_string())
+        .with_label("Spending Limit Check")
+        .call(PROXY)
+        .unwrap();
+    assert_eq!(check.hook().unwrap(), hook.contract_addr);
+
+    assert!(pre_tx_check(
+        &app,
+        &check.contract_addr,
+        vec![send(LIMIT, DENOM)]
+    ));
+    post_tx_hook(&app, &hook.contract_addr, PROXY, vec![send(LIMIT, DENOM)]).unwrap();
+    assert!(!pre_tx_check(
+        &app,
+        &check.contract_addr,
+        vec![send(1, DENOM)]
+    ));
+
+    pass_time(&app, PERIOD);
+    assert!(pre_tx_check(
+        &app,
+        &check.contract_addr,
+        vec![send(1, DENOM)]
+    ));
+}
@@ -0,0 +1,256 @@
+use cosmwasm_std::{BankMsg, IbcMsg, IbcTimeout, WasmMsg};
+use vectis_tx_history::{
+    error::TxHistoryError,
+    types::{MsgKind, MsgSummary, Retention, TxRecordResponse},
+};
+
+use crate::unit_tests::utils::*;
+
+const PROXY: &str = "proxy";
+const ALICE: &str = "alice";
+const BOB: &str = "bob";
+const INTERVAL: u64 = 100;
+
+fn retention(max_records: u32, max_age: Option<u64>) -> Retention {
+    Retention {
+        max_records,
+        max_age,
+    }
+}
+
+fn send(to: &str, amount: u128) -> CosmosMsg {
+    CosmosMsg::Bank(BankMsg::Send {
+        to_address: to.into(),
+        amount: vec![coin(amount, DENOM)],
+    })
+}
+
+fn record_ids(records: Vec<TxRecordResponse>) -> Vec<u64> {
+    records.into_iter().map(|r| r.id).collect()
+}
+
+#[test]
+fn executed_msgs_are_summarised() {
+    let app = App::default();
+    let plugin = TxHistoryCodeId::store_code(&app)
+        .instantiate(retention(10, None))
+        .with_label("Tx History")
+        .call(PROXY)
+        .unwrap();
+
+    let block = app.app().block_info();
+    post_tx_hook(
+        &app,
+        &plugin.contract_addr,
+        PROXY,
+        vec![
+            send(ALICE, 1),
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: BOB.into(),
+                msg: to_binary(&Empty {}).unwrap(),
+                funds: vec![coin(2, DENOM), coin(3, DENOM1)],
+            }),
+            CosmosMsg::Ibc(IbcMsg::Transfer {
+                channel_id: "channel-0".into(),
+                to_address: "cosmos1alice".into(),
+                amount: coin(4, DENOM),
+                timeout: IbcTimeout::with_timestamp(block.time.plus_seconds(INTERVAL)),
+            }),
+        ],
+    )
+    .unwrap();
+
+    let record = plugin.record(1).unwrap().record;
+    assert_eq!(record.height, block.height);
+    assert_eq!(record.time, block.time);
+    assert_eq!(
+        record.msgs,
+        vec![
+            MsgSummary {
+                kind: MsgKind::BankSend,
+                counterparty: Some(ALICE.into()),
+                funds: vec![coin(1, DENOM)],
+            },
+            MsgSummary {
+                kind: MsgKind::WasmExecute,
+                counterparty: Some(BOB.into()),
+                funds: vec![coin(2, DENOM), coin(3, DENOM1)],
+            },
+            MsgSummary {
+                kind: MsgKind::IbcTransfer,
+                counterparty: Some("cosmos1alice".into()),
+                funds: vec![coin(4, DENOM)],
+            },
+        ]
+    );
+}
+
+#[test]
+fn history_is_queryable_by_time_and_counterparty() {
+    let app = App::default();
+    let plugin = TxHistoryCodeId::store_code(&app)
+        .instantiate(retention(10, None))
+        .with_label("Tx History")
+        .call(PROXY)
+        .unwrap();
+
+    let start = app.app().block_info().time;
+    post_tx_hook(&app, &plugin.contract_addr, PROXY, vec![send(ALICE, 1)]).unwrap();
+    pass_time(&app, INTERVAL);
+    post_tx_hook(
+        &app,
+        &plugin.contract_addr,
+        PROXY,
+        vec![send(BOB, 1), send(&BOB.to_uppercase(), 2)],
+    )
+    .unwrap();
+    pass_time(&app, INTERVAL);
+    post_tx_hook(
+        &app,
+        &plugin.contract_addr,
+        PROXY,
+        vec![send(ALICE, 1), send(BOB, 1)],
+    )
+    .unwrap();
+
+    // Newest first
+    let records = plugin.history(None, None).unwrap().records;
+    assert_eq!(record_ids(records), vec![3, 2, 1]);
+    let records = plugin.history(Some(3), Some(1)).unwrap().records;
+    assert_eq!(record_ids(records), vec![2]);
+
+    let records = plugin
+        .history_by_time(Some(start.plus_seconds(1)), None, None, None)
+        .unwrap()
+        .records;
+    assert_eq!(record_ids(records), vec![2, 3]);
+    let records = plugin
+        .history_by_time(None, Some(start.plus_seconds(INTERVAL)), None, None)
+        .unwrap()
+        .records;
+    assert_eq!(record_ids(records), vec![1, 2]);
+    let records = plugin
+        .history_by_time(Some(start), None, Some(1), Some(1))
+        .unwrap()
+        .records;
+    assert_eq!(record_ids(records), vec![2]);
+    assert!(plugin
+        .history_by_time(None, None, Some(u64::MAX), None)
+        .unwrap()
+        .records
+        .is_empty());
+    assert!(plugin
+        .history_by_time(Some(start.plus_seconds(INTERVAL * 3)), None, None, None)
+        .unwrap()
+        .records
+        .is_empty());
+
+    // Each record is listed once per counterparty, regardless of case
+    let records = plugin
+        .history_by_counterparty(BOB.to_uppercase(), None, None)
+        .unwrap()
+        .records;
+    assert_eq!(record_ids(records), vec![3, 2]);
+    let records = plugin
+        .history_by_counterparty(ALICE.into(), Some(3), None)
+        .unwrap()
+        .records;
+    assert_eq!(record_ids(records), vec![1]);
+}
+
+#[test]
+fn oldest_records_are_pruned_by_retention() {
+    let app = App::default();
+    let plugin = TxHistoryCodeId::store_code(&app)
+        .instantiate(retention(2, None))
+        .with_label("Tx History")
+        .call(PROXY)
+        .unwrap();
+
+    post_tx_hook(&app, &plugin.contract_addr, PROXY, vec![send(ALICE, 1)]).unwrap();
+    post_tx_hook(&app, &plugin.contract_addr, PROXY, vec![send(BOB, 1)]).unwrap();
+    post_tx_hook(&app, &plugin.contract_addr, PROXY, vec![send(BOB, 1)]).unwrap();
+
+    let records = plugin.history(None, None).unwrap().records;
+    assert_eq!(record_ids(records), vec![3, 2]);
+    plugin.record(1).unwrap_err();
+    assert!(plugin
+        .history_by_counterparty(ALICE.into(), None, None)
+        .unwrap()
+        .records
+        .is_empty());
+
+    plugin
+        .update_retention(retention(10, Some(INTERVAL)))
+        .call(PROXY)
+        .unwrap();
+    pass_time(&app, INTERVAL);
+    post_tx_hook(&app, &plugin.contract_addr, PROXY, vec![send(ALICE, 1)]).unwrap();
+
+    let records = plugin.history(None, None).unwrap().records;
+    assert_eq!(record_ids(records), vec![4]);
+    let records = plugin
+        .history_by_time(None, None, None, None)
+        .unwrap()
+        .records;
+    assert_eq!(record_ids(records), vec![4]);
+
+    // Records can be kept forever
+    plugin
+        .update_retention(retention(10, Some(u64::MAX)))
+        .call(PROXY)
+        .unwrap();
+    pass_time(&app, INTERVAL);
+    post_tx_hook(&app, &plugin.contract_addr, PROXY, vec![send(ALICE, 1)]).unwrap();
+    let records = plugin.history(None, None).unwrap().records;
+    assert_eq!(record_ids(records), vec![5, 4]);
+}
+
+#[test]
+fn only_owner_can_record_and_update_retention() {
+    let app = App::default();
+
+    let err = TxHistoryCodeId::store_code(&app)
+        .instantiate(retention(0, None))
+        .with_label("Tx History")
+        .call(PROXY)
+        .unwrap_err();
+    assert_eq!(
+        err,
+        TxHistoryError::InvalidRetention("max_records must be greater than 0".into())
+    );
+
+    let plugin = TxHistoryCodeId::store_code(&app)
+        .instantiate(retention(10, None))
+        .with_label("Tx History")
+        .call(PROXY)
+        .unwrap();
+    assert_eq!(plugin.owner().unwrap(), Addr::unchecked(PROXY));
+
+    let err = post_tx_hook(&app, &plugin.contract_addr, ALICE, vec![send(ALICE, 1)]).unwrap_err();
+    assert_eq!(
+        err.downcast::<TxHistoryError>().unwrap(),
+        TxHistoryError::Unauthorized
+    );
+
+    let err = plugin
+        .update_retention(retention(1, None))
+        .call(ALICE)
+        .unwrap_err();
+    assert_eq!(err, TxHistoryError::Unauthorized);
+
+    let err = plugin
+        .update_retention(retention(1, Some(0)))
+        .call(PROXY)
+        .unwrap_err();
+    assert_eq!(
+        err,
+        TxHistoryError::InvalidRetention("max_age must be greater than 0".into())
+    );
+
+    plugin
+        .update_retention(retention(1, Some(INTERVAL)))
+        .call(PROXY)
+        .unwrap();
+    assert_eq!(plugin.retention().unwrap(), retention(1, Some(INTERVAL)));
+}
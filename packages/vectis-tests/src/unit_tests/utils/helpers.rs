@@ -14,6 +14,71 @@ pub fn contract_cw4() -> Box<dyn Contract<Empty>> {
     Box::new(contract)
 }
 
+/// A stand-in proxy that executes all the msgs it receives through `PluginExecute`,
+/// this is useful for testing plugins without going through the registry install flow
+pub fn contract_mock_proxy() -> Box<dyn Contract<Empty>> {
+    let contract =
+        ContractWrapper::new(mock_proxy_execute, mock_proxy_instantiate, mock_proxy_query);
+    Box::new(contract)
+}
+
+fn mock_proxy_execute(
+    _deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    msg: WalletPluginExecMsg,
+) -> StdResult<Response> {
+    match msg {
+        WalletPluginExecMsg::PluginExecute { msg } => Ok(Response::new().add_messages(msg)),
+        _ => Err(StdError::generic_err(
+            "mock proxy only supports PluginExecute",
+        )),
+    }
+}
+
+fn mock_proxy_instantiate(
+    _deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    _msg: Empty,
+) -> StdResult<Response> {
+    Ok(Response::new())
+}
+
+fn mock_proxy_query(_deps: Deps, _env: Env, _msg: Empty) -> StdResult<Binary> {
+    Err(StdError::generic_err("mock proxy has no queries"))
+}
+
+pub fn pass_time(app: &App<MtApp>, seconds: u64) {
+    app.app_mut().update_block(|block| {
+        block.time = block.time.plus_seconds(seconds);
+        block.height += 1;
+    });
+}
+
+/// Calls `post_tx_hook` on a plugin as `sender`
+pub fn post_tx_hook(
+    app: &App<MtApp>,
+    plugin: &Addr,
+    sender: &str,
+    msgs: Vec<CosmosMsg>,
+) -> AnyResult<()> {
+    app.app_mut().execute_contract(
+        Addr::unchecked(sender),
+        plugin.clone(),
+        &PostTxHookExecMsg::PostTxHook { msgs },
+        &[],
+    )?;
+    Ok(())
+}
+
+pub fn pre_tx_check(app: &App<MtApp>, plugin: &Addr, msgs: Vec<CosmosMsg>) -> bool {
+    app.app()
+        .wrap()
+        .query_wasm_smart(plugin.clone(), &PreTxCheckQueryMsg::PreTxCheck { msgs })
+        .unwrap()
+}
+
 pub fn add_item_msg(key: VectisActors, value: Addr) -> cw3flexExecMsg {
     cw3flexExecMsg::UpdateItem {
         key: format!("{key}"),
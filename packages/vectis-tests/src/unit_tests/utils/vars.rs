@@ -1,7 +1,7 @@
 pub use cosmwasm_std::{
     coin, instantiate2_address, to_binary, Addr, Api, Binary, BlockInfo, CanonicalAddr, Coin,
-    CosmosMsg, Empty, QueryRequest, RecoverPubkeyError, StdError, StdResult, Storage, Timestamp,
-    Uint128, VerificationError, WasmQuery,
+    CosmosMsg, Deps, DepsMut, Empty, Env, MessageInfo, QueryRequest, RecoverPubkeyError, Response,
+    StdError, StdResult, Storage, Timestamp, Uint128, VerificationError, WasmQuery,
 };
 pub use cw_multi_test::{
     error::AnyResult, AddressGenerator, App as MtApp, AppBuilder, BankKeeper, Contract,
@@ -24,6 +24,8 @@ pub use cw4_group::{
 };
 
 pub use test_vectis_pre_tx::contract::sv::multitest_utils::CodeId as TestPreTxPluginCodeId;
+pub use vectis_compliance::contract::sv::multitest_utils::CodeId as ComplianceCodeId;
+pub use vectis_compliance_denylist::contract::sv::multitest_utils::CodeId as ComplianceDenylistCodeId;
 pub use vectis_factory::{
     contract::sv::multitest_utils::{CodeId as FactoryCodeId, VectisFactoryProxy},
     management::contract::sv::test_utils::*,
@@ -38,11 +40,18 @@ pub use vectis_proxy::{
     contract::sv::multitest_utils::{CodeId as ProxyCodeId, VectisProxyProxy},
     wallet::contract::sv::test_utils::WalletTrait,
 };
+pub use vectis_recurring_payments::contract::sv::multitest_utils::CodeId as RecurringPaymentsCodeId;
+pub use vectis_spending_limit_check::contract::sv::multitest_utils::CodeId as SpendingLimitCheckCodeId;
+pub use vectis_spending_limit_hook::contract::sv::multitest_utils::CodeId as SpendingLimitHookCodeId;
+pub use vectis_tx_history::contract::sv::multitest_utils::CodeId as TxHistoryCodeId;
 pub use vectis_wallet::{
     interface::{
+        post_tx_hook_trait::sv::ExecMsg as PostTxHookExecMsg,
+        pre_tx_check_trait::sv::QueryMsg as PreTxCheckQueryMsg,
         registry_service_trait::sv::{
             ExecMsg as RegistryServiceExecMsg, QueryMsg as RegistryServiceQueryMsg,
         },
+        wallet_plugin_trait::sv::ExecMsg as WalletPluginExecMsg,
         wallet_trait::sv::{ExecMsg as WalletExecMsg, QueryMsg as WalletQueryMsg},
     },
     types::{